@@ -1,9 +1,7 @@
-use char_index::IndexedChars;
+use char_index::{IndexedChars, IndexedChars32, InlineIndexedChars};
 
-use core::hint::black_box;
-
-use criterion::{criterion_group, criterion_main, Criterion};
-use rand::{seq::SliceRandom, thread_rng};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use rand::{seq::SliceRandom, thread_rng, Rng};
 
 pub fn perf(c: &mut Criterion) {
     let mut base = ['e'; 1000]
@@ -33,11 +31,130 @@ pub fn perf(c: &mut Criterion) {
 
     println!(
         "IndexedChars: {} bytes",
-        indexed.len() + indexed.chars().count()
+        indexed.len() + indexed.memory_footprint()
     );
     println!("String: {} bytes", indexed.len());
     println!("Vec<char>: {} bytes", 4 * indexed.chars().count());
 }
 
-criterion_group!(benches, perf);
+pub fn construct(c: &mut Criterion) {
+    let base_str = "föobär💯".repeat(100_000);
+    let ascii_str = "foobar!".repeat(100_000);
+
+    // run with no features, simd, and simd with unsafe to compare char counting
+    let mut group = c.benchmark_group("construct 700k chars");
+
+    group.bench_function("indexed_chars", |b| {
+        b.iter(|| black_box(IndexedChars::new(black_box(&base_str))))
+    });
+    // indexing an ascii string is only the char count
+    group.bench_function("indexed_chars_ascii", |b| {
+        b.iter(|| black_box(IndexedChars::new(black_box(&ascii_str))))
+    });
+    group.bench_function("char_count", |b| {
+        b.iter(|| black_box(black_box(&base_str).chars().count()))
+    });
+}
+
+pub fn sequential(c: &mut Criterion) {
+    // many rollovers, so each get_char has a real binary search to do
+    let base_str = "föobär💯".repeat(10_000);
+    let indexed = IndexedChars::new(&base_str);
+    let len = indexed.len_chars();
+
+    let mut group = c.benchmark_group("sequential 70k chars");
+
+    group.bench_function("get_char", |b| {
+        b.iter(|| {
+            for i in 0..len {
+                black_box(indexed.get_char(i));
+            }
+        })
+    });
+    group.bench_function("cursor", |b| {
+        b.iter(|| {
+            let mut cursor = indexed.cursor();
+
+            for i in 0..len {
+                black_box(cursor.advance_to(i));
+            }
+        })
+    });
+}
+
+pub fn short(c: &mut Criterion) {
+    let short_str = "föobär💯";
+
+    // short non ascii strings are where the offsets allocation dominates
+    let mut group = c.benchmark_group("construct short");
+
+    group.bench_function("indexed_chars", |b| {
+        b.iter(|| black_box(IndexedChars::new(black_box(short_str))))
+    });
+    group.bench_function("inline_indexed_chars", |b| {
+        b.iter(|| black_box(InlineIndexedChars::<16>::new(black_box(short_str))))
+    });
+}
+
+pub fn rollovers(c: &mut Criterion) {
+    let mut group = c.benchmark_group("random lookup by rollover count");
+
+    for rollovers in [0, 10, 1000] {
+        // every 'ö' adds 1 to the offset, so u8 offsets roll over every 255 of them
+        let base_str = "ö".repeat(255 * rollovers + 200);
+        let indexed = IndexedChars::new(&base_str);
+        let wide = IndexedChars32::new_with_width(&base_str);
+        assert_eq!(indexed.rollover_count(), rollovers);
+
+        // random indices so the search path can not be learned by the branch predictor
+        let mut rng = thread_rng();
+        let indices: Vec<usize> = (0..1000)
+            .map(|_| rng.gen_range(0..indexed.len_chars()))
+            .collect();
+
+        group.bench_function(format!("u8 {rollovers} rollovers"), |b| {
+            b.iter(|| {
+                for &i in &indices {
+                    black_box(indexed.get_char(black_box(i)));
+                }
+            })
+        });
+        // never rolls over, the baseline without any search
+        group.bench_function(format!("u32 {rollovers} rollovers"), |b| {
+            b.iter(|| {
+                for &i in &indices {
+                    black_box(wide.get_char(black_box(i)));
+                }
+            })
+        });
+
+        // the char index of every rollover the u8 index stores, each 'ö' offsets the next char by one more byte,
+        // so the kth rollover happens once the offset passes k * 255
+        let positions: Vec<usize> = (1..=rollovers).map(|k| 255 * k + 1).collect();
+
+        // the two ways of counting the rollovers that apply to a char index, the rest of the lookup is the same
+        group.bench_function(format!("binary_search {rollovers} rollovers"), |b| {
+            b.iter(|| {
+                for &i in &indices {
+                    // only correct when no two rollovers share an index, which holds for chars but not grapheme clusters
+                    black_box(
+                        positions
+                            .binary_search(&black_box(i))
+                            .map_or_else(|e| e, |t| t + 1),
+                    );
+                }
+            })
+        });
+        group.bench_function(format!("partition_point {rollovers} rollovers"), |b| {
+            b.iter(|| {
+                for &i in &indices {
+                    let i = black_box(i);
+                    black_box(positions.partition_point(|&rollover| rollover <= i));
+                }
+            })
+        });
+    }
+}
+
+criterion_group!(benches, perf, construct, sequential, short, rollovers);
 criterion_main!(benches);
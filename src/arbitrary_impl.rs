@@ -0,0 +1,35 @@
+//! Module containing the optional `arbitrary` trait implementation, for fuzzing code that takes an indexed string.
+
+use alloc::string::String;
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+use crate::{offset::OffsetInt, OwnedIndexedChars};
+
+impl<'a, O: OffsetInt> Arbitrary<'a> for OwnedIndexedChars<O> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        // an arbitrary String is whatever prefix of the input is valid utf8, which is mostly ascii,
+        // generating each char separately gives many multi byte chars, and so rollovers
+        let len = u.arbitrary_len::<char>()?;
+        let buf = (0..len)
+            .map(|_| u.arbitrary::<char>())
+            .collect::<Result<String>>()?;
+
+        Ok(OwnedIndexedChars::new_with_width(buf))
+    }
+}
+
+#[test]
+fn arbitrary() {
+    use alloc::vec::Vec;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    // seeded so the generated length is never 0
+    let mut rng = StdRng::seed_from_u64(0);
+    let data: Vec<u8> = (0..4096).map(|_| rng.gen()).collect();
+    let mut u = Unstructured::new(&data);
+
+    let index = OwnedIndexedChars::<u8>::arbitrary(&mut u).unwrap();
+
+    assert!(index.validate());
+    assert!(index.len() > index.char_count());
+}
@@ -1,61 +1,1182 @@
 //! Module containing [`IndexedChars`] and its trait implementations
 
-use crate::IndexedCharsInner;
+use crate::{
+    offset::OffsetInt, CharChunks, CharCursor, CharIndex, CharIndexError, CharIndices, CharWindows,
+    Chars, IndexedCharsInner, OwnedIndexedChars,
+};
+
+#[cfg(feature = "unicode-width")]
+use crate::indexed_chars::display_width;
+#[cfg(feature = "unicode-normalization")]
+use crate::indexed_chars::normalize_nfc;
+#[cfg(feature = "unicode-normalization")]
+use alloc::borrow::Cow;
+use alloc::{string::String, vec::Vec};
 use core::{
     borrow::Borrow,
     cmp::{Eq, Ord, Ordering, PartialEq, PartialOrd},
     fmt,
     hash::{Hash, Hasher},
-    ops::Deref,
+    iter::Rev,
+    ops::{Deref, Range},
+    str::{self, Utf8Error},
 };
 
-/// A string whose char indices have been cached for ~O(1) char lookup.  
-///
-/// This structure allocates 1 additional bytes per unicode scalar value,
-/// which in the case of ascii will only use 2 total bytes for a
-/// single char (as opposed to the 4 bytes required in `Vec<char>`).
-///
-/// As the number of non ascii characters increases, the data density will worsen, until the potential worst case of 5 bytes per character.
-///
-/// The internal representation of this type allows for up to 255 bytes of non ascii unicode chars before an internal rollover occurs (thus tending the complexity towards O(log n)), this is the tradeoff made to reduce memory usage. See the section [`How it Works`](index.html#how-it-works) for details on why char indexing worst case is O(log n), and why in practical cases it appears to be O(1).
-///
-/// This type mimics a `&'a str` with its trait impls, including `Debug`, `Display`, `PartialEq` with `&str` `PartialOrd` with `&str`, `Hash`, and `AsRef`/`Borrow`.
-pub struct IndexedChars<'a> {
-    /// Backing string buffer
-    buf: &'a str,
-    /// Char offsets index
-    inner: IndexedCharsInner,
-}
+/// A string whose char indices have been cached for ~O(1) char lookup.  
+///
+/// This structure allocates 1 additional bytes per unicode scalar value,
+/// which in the case of ascii will only use 2 total bytes for a
+/// single char (as opposed to the 4 bytes required in `Vec<char>`).
+///
+/// As the number of non ascii characters increases, the data density will worsen, until the potential worst case of 5 bytes per character.
+///
+/// The internal representation of this type allows for up to 255 bytes of non ascii unicode chars before an internal rollover occurs (thus tending the complexity towards O(log n)), this is the tradeoff made to reduce memory usage. See the section [`How it Works`](index.html#how-it-works) for details on why char indexing worst case is O(log n), and why in practical cases it appears to be O(1).
+///
+/// For strings made up of mostly non ascii chars, the offsets can be stored in a wider [`OffsetInt`] type to make rollovers much rarer,
+/// at the cost of more memory per char, see [`IndexedChars16`](crate::IndexedChars16) and [`IndexedChars32`](crate::IndexedChars32).
+///
+/// This type mimics a `&'a str` with its trait impls, including `Debug`, `Display`, `PartialEq` with `String`, `PartialEq` and `PartialOrd` with `&str` and [`OwnedIndexedChars`](crate::OwnedIndexedChars), `Hash`, `AsRef<str>`/`AsRef<[u8]>`, and `Borrow`.
+///
+/// Comparing two indexed strings for equality checks their cached char counts first, and then compares the backing strings byte for byte,
+/// so it never decodes chars and rejects strings of different char counts in O(1).
+///
+/// `to_string` is available through `Display`, but `String::from` copies the backing string directly without going through the formatting machinery.
+pub struct IndexedChars<'a, O = u8> {
+    /// Backing string buffer
+    buf: &'a str,
+    /// Char offsets index
+    inner: InnerRef<'a, O>,
+    /// Whether `buf` is known to be the string `inner` was built from, unchecked lookups are only sound if it is.
+    /// This is only false for views of a [`SharedIndexedChars`](crate::SharedIndexedChars), whose string pointer may deref to a different string each time.
+    #[cfg(feature = "unsafe")]
+    verified: bool,
+}
+
+/// The char offsets index of an [`IndexedChars`], either built for it or borrowed from an owned instance.
+enum InnerRef<'a, O> {
+    /// Index built for this instance
+    Owned(IndexedCharsInner<O>),
+    /// Index borrowed from an [`OwnedIndexedChars`](crate::OwnedIndexedChars) or [`SharedIndexedChars`](crate::SharedIndexedChars)
+    Borrowed(&'a IndexedCharsInner<O>),
+}
+
+impl<O> Deref for InnerRef<'_, O> {
+    type Target = IndexedCharsInner<O>;
+
+    fn deref(&self) -> &IndexedCharsInner<O> {
+        match self {
+            InnerRef::Owned(inner) => inner,
+            InnerRef::Borrowed(inner) => inner,
+        }
+    }
+}
+
+impl<O> IndexedChars<'_, O> {
+    /// Returns the char offsets index of the backing string, for other indexed string types to compare against.
+    pub(crate) fn inner_ref(&self) -> &IndexedCharsInner<O> {
+        &self.inner
+    }
+}
+
+impl<'a> IndexedChars<'a> {
+    /// Constructs a new [`IndexedChars`] instance from a [`&str`]. This is O(n), but the cost should only be paid once ideally.
+    ///
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::IndexedChars;
+    /// let index = IndexedChars::new("foo");
+    /// # assert_eq!(index.get_char(0), Some('f'));
+    /// ```
+    #[must_use]
+    pub fn new(s: &'a str) -> Self {
+        Self::new_with_width(s)
+    }
+
+    /// Constructs a new [`IndexedChars`] instance like [`new`][IndexedChars::new], also indexing where the chars that take two UTF-16 code units are.
+    ///
+    /// This makes [`char_to_utf16`][IndexedChars::char_to_utf16] and [`utf16_to_char`][IndexedChars::utf16_to_char] O(log n) rather than O(n),
+    /// for bridging to UTF-16 based APIs on every lookup, such as the positions of an editor protocol.
+    /// The UTF-16 index takes a `usize` for every char above U+FFFF, and is carried over to views sliced from this one.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::IndexedChars;
+    /// let index = IndexedChars::with_utf16_index("f💯öo💯");
+    ///
+    /// assert_eq!(index.char_to_utf16(3), Some(4));
+    /// assert_eq!(index.utf16_to_char(4), Some(3));
+    /// assert_eq!(index.utf16_to_char(2), None);
+    /// assert_eq!(index.char_slice(2..5).unwrap().char_to_utf16(3), Some(4));
+    /// ```
+    #[must_use]
+    pub fn with_utf16_index(s: &'a str) -> Self {
+        let mut inner = IndexedCharsInner::new(s);
+        inner.index_utf16(s);
+
+        Self {
+            buf: s,
+            inner: InnerRef::Owned(inner),
+            #[cfg(feature = "unsafe")]
+            verified: true,
+        }
+    }
+
+    /// Constructs a new [`IndexedChars`] instance from an ascii string in a `const` context, such as a `static`.
+    ///
+    /// Ascii strings need no offsets to be stored, so this does not allocate.
+    /// Returns `None` if the string is not ascii, as the index of any other string must be built at runtime with [`new`][IndexedChars::new].
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::IndexedChars;
+    /// static KEYWORD: Option<IndexedChars<'static>> = IndexedChars::new_ascii("static");
+    ///
+    /// assert_eq!(KEYWORD.as_ref().and_then(|k| k.get_char(1)), Some('t'));
+    /// assert!(IndexedChars::new_ascii("föo").is_none());
+    /// ```
+    #[must_use]
+    pub const fn new_ascii(s: &'a str) -> Option<Self> {
+        let bytes = s.as_bytes();
+        let mut i = 0;
+
+        // str::is_ascii is not const on our MSRV
+        while i < bytes.len() {
+            if !bytes[i].is_ascii() {
+                return None;
+            }
+
+            i += 1;
+        }
+
+        Some(Self {
+            buf: s,
+            inner: InnerRef::Owned(IndexedCharsInner::new_ascii(s.len())),
+            #[cfg(feature = "unsafe")]
+            verified: true,
+        })
+    }
+
+    /// Constructs an empty [`IndexedChars`] instance in a `const` context, such as a `static`.
+    #[must_use]
+    pub const fn empty() -> Self {
+        Self {
+            buf: "",
+            inner: InnerRef::Owned(IndexedCharsInner::new_ascii(0)),
+            #[cfg(feature = "unsafe")]
+            verified: true,
+        }
+    }
+
+    /// Constructs a new [`IndexedChars`] instance from a byte slice, if it is valid UTF-8.
+    ///
+    /// # Errors
+    /// Returns the error from [`str::from_utf8`](core::str::from_utf8) if the bytes are not valid UTF-8.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::IndexedChars;
+    /// let index = IndexedChars::from_utf8("föo".as_bytes()).unwrap();
+    ///
+    /// assert_eq!(index.get_char(1), Some('ö'));
+    /// assert!(IndexedChars::from_utf8(&[0x66, 0xFF]).is_err());
+    /// ```
+    pub fn from_utf8(bytes: &'a [u8]) -> Result<Self, Utf8Error> {
+        str::from_utf8(bytes).map(Self::new)
+    }
+
+    /// Constructs a new [`IndexedChars`] instance like [`new`][IndexedChars::new], but splits the indexing work across the rayon thread pool.
+    ///
+    /// This is only worth it for very large strings, smaller ones are indexed on the current thread.
+    /// The resulting index is identical to the one [`new`][IndexedChars::new] would build.
+    /// Only available with the `rayon` feature enabled.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::IndexedChars;
+    /// let s = "föobär".repeat(100_000);
+    /// let index = IndexedChars::new_parallel(&s);
+    ///
+    /// assert_eq!(index.get_char(300_004), Some('ä'));
+    /// ```
+    #[cfg(feature = "rayon")]
+    #[must_use]
+    pub fn new_parallel(s: &'a str) -> Self {
+        let inner = IndexedCharsInner::new_parallel(s);
+
+        Self {
+            buf: s,
+            inner: InnerRef::Owned(inner),
+            #[cfg(feature = "unsafe")]
+            verified: true,
+        }
+    }
+}
+
+impl<'a, O: OffsetInt> IndexedChars<'a, O> {
+    /// Constructs a new [`IndexedChars`] instance from a [`&str`], storing its offsets in `O`.
+    ///
+    /// This is the same as [`new`][IndexedChars::new] for any offset width, and is mainly useful through the wider type aliases.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::IndexedChars16;
+    /// let index = IndexedChars16::new_with_width("föo");
+    ///
+    /// assert_eq!(index.get_char(1), Some('ö'));
+    /// ```
+    #[must_use]
+    pub fn new_with_width(s: &'a str) -> Self {
+        let inner = IndexedCharsInner::new(s);
+
+        Self {
+            buf: s,
+            inner: InnerRef::Owned(inner),
+            #[cfg(feature = "unsafe")]
+            verified: true,
+        }
+    }
+
+    /// Constructs a new [`IndexedChars`] instance from a [`&str`] whose char count is already known, such as from a previous index of it.
+    ///
+    /// This skips the pass over the string that [`new`][IndexedChars::new] makes to count its chars, so indexing takes one pass instead of two.
+    /// The count is checked with a debug assertion, in release builds a wrong count costs the time saved but still builds a correct index.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::IndexedChars;
+    /// let s = "föobär";
+    /// let index: IndexedChars = IndexedChars::new_with_char_count(s, 6);
+    ///
+    /// assert_eq!(index.get_char(4), Some('ä'));
+    /// ```
+    #[must_use]
+    pub fn new_with_char_count(s: &'a str, char_count: usize) -> Self {
+        let inner = IndexedCharsInner::new_with_char_count(s, char_count);
+
+        Self {
+            buf: s,
+            inner: InnerRef::Owned(inner),
+            #[cfg(feature = "unsafe")]
+            verified: true,
+        }
+    }
+
+    /// Creates an [`IndexedChars`] that borrows an already built index instead of building its own,
+    /// the index passed must be the one built for `buf`.
+    pub(crate) fn from_borrowed(buf: &'a str, inner: &'a IndexedCharsInner<O>) -> Self {
+        Self {
+            buf,
+            inner: InnerRef::Borrowed(inner),
+            #[cfg(feature = "unsafe")]
+            verified: true,
+        }
+    }
+
+    /// Creates an [`IndexedChars`] that borrows an already built index like [`from_borrowed`][IndexedChars::from_borrowed],
+    /// for a string that may not be the one the index was built from, so unchecked lookups fall back to checked ones.
+    pub(crate) fn from_unverified(buf: &'a str, inner: &'a IndexedCharsInner<O>) -> Self {
+        Self {
+            buf,
+            inner: InnerRef::Borrowed(inner),
+            #[cfg(feature = "unsafe")]
+            verified: false,
+        }
+    }
+
+    /// Indexes into the backing string to retrieve the nth codepoint.
+    ///
+    /// This operation has an average case of O(1), and a worst case of O(log n).
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::IndexedChars;
+    /// assert_eq!(IndexedChars::new("foobar").get_char(3), Some('b'));
+    /// ```
+    #[must_use]
+    pub fn get_char(&self, index: usize) -> Option<char> {
+        self.inner.get_char(self.buf, index)
+    }
+
+    /// Indexes into the backing string to retrieve the nth codepoint, or returns `default` if the index is out of bounds.
+    ///
+    /// This is the same as [`get_char`][IndexedChars::get_char] followed by [`Option::unwrap_or`], for code that always needs a char, such as a placeholder.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::IndexedChars;
+    /// let index = IndexedChars::new("föo");
+    ///
+    /// assert_eq!(index.get_char_or(1, '_'), 'ö');
+    /// assert_eq!(index.get_char_or(3, '_'), '_');
+    /// ```
+    #[must_use]
+    pub fn get_char_or(&self, index: usize, default: char) -> char {
+        self.get_char(index).unwrap_or(default)
+    }
+
+    /// Indexes into the backing string to retrieve the nth char, alongside the byte range it spans in the backing string.
+    ///
+    /// Returns `None` if the index is out of bounds.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::IndexedChars;
+    /// let index = IndexedChars::new("föo");
+    ///
+    /// assert_eq!(index.char_and_span(1), Some(('ö', 1..3)));
+    /// assert_eq!(index.char_and_span(2), Some(('o', 3..4)));
+    /// assert_eq!(index.char_and_span(3), None);
+    /// ```
+    #[must_use]
+    pub fn char_and_span(&self, index: usize) -> Option<(char, Range<usize>)> {
+        let start = self.char_to_byte(index)?;
+        let c = self.buf[start..].chars().next()?;
+
+        Some((c, start..start + c.len_utf8()))
+    }
+
+    /// Returns `true` if the char at `index` is `c`, and `false` if it is not or the index is out of bounds.
+    ///
+    /// Prefix and suffix checks such as `starts_with` are available through `Deref` to `str`, which accepts any pattern.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::IndexedChars;
+    /// let index = IndexedChars::new("föo");
+    ///
+    /// assert!(index.nth_char_is(1, 'ö'));
+    /// assert!(!index.nth_char_is(1, 'o'));
+    /// assert!(!index.nth_char_is(3, 'o'));
+    /// assert!(index.starts_with("fö") && index.ends_with('o'));
+    /// ```
+    #[must_use]
+    pub fn nth_char_is(&self, index: usize, c: char) -> bool {
+        self.get_char(index) == Some(c)
+    }
+
+    /// Retrieves the chars at several char indices at once, as if by calling [`get_char`][IndexedChars::get_char] for each of them.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::IndexedChars;
+    /// let index = IndexedChars::new("föo");
+    ///
+    /// assert_eq!(index.get_many([1, 0, 7]), [Some('ö'), Some('f'), None]);
+    /// ```
+    #[must_use]
+    pub fn get_many<const N: usize>(&self, indices: [usize; N]) -> [Option<char>; N] {
+        let mut chars = [None; N];
+
+        for (c, index) in chars.iter_mut().zip(indices) {
+            *c = self.get_char(index);
+        }
+
+        chars
+    }
+
+    /// Returns a [`CharCursor`] at the start of the backing string, for looking up chars near each other faster than [`get_char`][IndexedChars::get_char].
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::IndexedChars;
+    /// let index = IndexedChars::new("föo");
+    /// let mut cursor = index.cursor();
+    ///
+    /// let chars: Vec<_> = (0..index.len_chars()).map(|i| cursor.advance_to(i).unwrap()).collect();
+    ///
+    /// assert_eq!(chars, ['f', 'ö', 'o']);
+    /// ```
+    #[must_use]
+    pub fn cursor(&self) -> CharCursor<'_, O> {
+        CharCursor::new(self.buf, &self.inner)
+    }
+
+    /// Indexes into the backing string to retrieve the nth codepoint, panicking if it is out of bounds.
+    ///
+    /// This is the infallible counterpart to [`get_char`][IndexedChars::get_char], for indexes that are known to be valid.
+    ///
+    /// # Panics
+    /// Panics if `index` is not less than [`char_count`][IndexedChars::char_count].
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::IndexedChars;
+    /// let s = IndexedChars::new("föo");
+    ///
+    /// assert_eq!(s.char_at(1), 'ö');
+    /// ```
+    ///
+    /// ```rust,should_panic
+    /// # use char_index::IndexedChars;
+    /// let s = IndexedChars::new("föo");
+    ///
+    /// // panics with "char index 3 out of bounds (len 3)"
+    /// let _ = s.char_at(3);
+    /// ```
+    #[must_use]
+    #[track_caller]
+    pub fn char_at(&self, index: usize) -> char {
+        match self.get_char(index) {
+            Some(c) => c,
+            None => panic!(
+                "char index {} out of bounds (len {})",
+                index,
+                self.char_count()
+            ),
+        }
+    }
+
+    /// Returns the first char of the backing string, or `None` if it is empty.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::IndexedChars;
+    /// let index = IndexedChars::new("foö");
+    ///
+    /// assert_eq!(index.first_char(), Some('f'));
+    /// assert_eq!(index.last_char(), Some('ö'));
+    /// ```
+    #[must_use]
+    pub fn first_char(&self) -> Option<char> {
+        self.buf.chars().next()
+    }
+
+    /// Returns the last char of the backing string in O(1), or `None` if it is empty.
+    #[must_use]
+    pub fn last_char(&self) -> Option<char> {
+        self.buf.chars().next_back()
+    }
+
+    /// Indexes into the backing string to retrieve the nth codepoint, without checking that the index is in bounds.
+    ///
+    /// This is the same as [`get_char`][IndexedChars::get_char] but skips its bounds checks, for hot loops where the index is already known to be valid.
+    /// Only available with the `unsafe` feature enabled.
+    ///
+    /// # Safety
+    /// `index` must be less than [`char_count`][IndexedChars::char_count], calling this with an out of bounds index is undefined behavior.
+    ///
+    /// # Panics
+    /// Views of a [`SharedIndexedChars`](crate::SharedIndexedChars), and views sliced from them, can not trust their string pointer
+    /// to deref to the same string every time, so they use a checked lookup instead, which panics if the index is out of bounds.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::IndexedChars;
+    /// let s = IndexedChars::new("föo");
+    ///
+    /// for i in 0..s.char_count() {
+    ///     // SAFETY: i is below the char count
+    ///     let c = unsafe { s.get_char_unchecked(i) };
+    ///     # assert_eq!(Some(c), s.get_char(i));
+    /// }
+    /// ```
+    #[cfg(feature = "unsafe")]
+    #[allow(unsafe_code)]
+    #[must_use]
+    pub unsafe fn get_char_unchecked(&self, index: usize) -> char {
+        if !self.verified {
+            return self.get_char(index).expect("char index out of bounds");
+        }
+
+        // SAFETY: caller guarantees index is in bounds, and a verified index always matches our backing string
+        self.inner.get_char_unchecked(self.buf, index)
+    }
+
+    /// Converts a char index into the byte index where that char starts in the backing string.
+    ///
+    /// Returns `None` if the char index is out of bounds, this operation has the same complexity as [`get_char`][IndexedChars::get_char].
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::IndexedChars;
+    /// let index = IndexedChars::new("föo");
+    ///
+    /// assert_eq!(index.char_to_byte(2), Some(3));
+    /// assert_eq!(&index[index.char_to_byte(2).unwrap()..], "o");
+    /// assert_eq!(index.char_to_byte(3), None);
+    /// ```
+    #[must_use]
+    pub fn char_to_byte(&self, index: usize) -> Option<usize> {
+        self.inner.char_to_byte(self.buf, index)
+    }
+
+    /// Returns an iterator over the byte index where each char starts in the backing string, in char order.
+    ///
+    /// This yields what [`char_to_byte`][IndexedChars::char_to_byte] returns for every index,
+    /// but walks the index once instead of searching it for each char.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::IndexedChars;
+    /// let index = IndexedChars::new("föo!");
+    ///
+    /// assert!(index.char_offsets().eq([0, 1, 3, 4]));
+    /// ```
+    pub fn char_offsets(&self) -> impl Iterator<Item = usize> + '_ {
+        self.inner.char_offsets()
+    }
+
+    /// Returns the byte index of the boundary before the nth char, suitable for passing to [`str::split_at`].
+    ///
+    /// This is the same as [`char_to_byte`][IndexedChars::char_to_byte], except that `char_index == len_chars`
+    /// is also accepted and returns the length of the backing string in bytes.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::IndexedChars;
+    /// let index = IndexedChars::new("föo");
+    ///
+    /// assert_eq!(index.nth_char_boundary(1), Some(1));
+    /// assert_eq!(index.nth_char_boundary(3), Some(4));
+    /// assert_eq!(index.split_at(index.nth_char_boundary(2).unwrap()), ("fö", "o"));
+    /// assert_eq!(index.nth_char_boundary(4), None);
+    /// ```
+    #[must_use]
+    pub fn nth_char_boundary(&self, char_index: usize) -> Option<usize> {
+        self.inner.char_boundary(self.buf, char_index)
+    }
+
+    /// Divides the backing string into two substrings at a char index, like [`str::split_at`] but indexed by chars.
+    ///
+    /// Returns `None` if `char_index` is greater than [`len_chars`][IndexedChars::len_chars],
+    /// `char_index == len_chars` returns the whole string and an empty string.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::IndexedChars;
+    /// let index = IndexedChars::new("föo");
+    ///
+    /// assert_eq!(index.split_at_char(1), Some(("f", "öo")));
+    /// assert_eq!(index.split_at_char(3), Some(("föo", "")));
+    /// assert_eq!(index.split_at_char(4), None);
+    /// ```
+    #[must_use]
+    pub fn split_at_char(&self, char_index: usize) -> Option<(&'a str, &'a str)> {
+        let mid = self.nth_char_boundary(char_index)?;
+
+        Some(self.buf.split_at(mid))
+    }
+
+    /// Checks that the backing string is equal to `other`, ignoring ascii case, without allocating.
+    ///
+    /// This is the same as [`str::eq_ignore_ascii_case`], non ascii chars must match exactly.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::IndexedChars;
+    /// let index = IndexedChars::new("föo");
+    ///
+    /// assert!(index.eq_ignore_ascii_case("FöO"));
+    /// assert!(!index.eq_ignore_ascii_case("FÖO"));
+    /// ```
+    #[must_use]
+    pub fn eq_ignore_ascii_case(&self, other: &str) -> bool {
+        self.buf.eq_ignore_ascii_case(other)
+    }
+
+    /// Returns `true` if the chars of the backing string are exactly the chars yielded by `iter`, without collecting them into a string.
+    ///
+    /// Iteration stops at the first mismatch, and when `iter` reports an exact length that differs from the char count, it is not iterated at all.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::IndexedChars;
+    /// let index = IndexedChars::new("föo");
+    ///
+    /// assert!(index.eq_chars(['f', 'ö', 'o']));
+    /// assert!(index.eq_chars("FÖO".chars().flat_map(char::to_lowercase)));
+    /// assert!(!index.eq_chars("föob".chars()));
+    /// ```
+    #[must_use]
+    pub fn eq_chars<I: IntoIterator<Item = char>>(&self, iter: I) -> bool {
+        let iter = iter.into_iter();
+
+        // the cached char count can rule out iterators of a known different length up front
+        if let (lower, Some(upper)) = iter.size_hint() {
+            if lower == upper && lower != self.char_count() {
+                return false;
+            }
+        }
+
+        self.chars().eq(iter)
+    }
+
+    /// Returns the backing string in Unicode Normalization Form C, borrowing it if it is already normalized.
+    ///
+    /// No index is built for the normalized string, index it separately if char access is needed.
+    /// Only available with the `unicode-normalization` feature enabled.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::IndexedChars;
+    /// let index = IndexedChars::new("fo\u{308}o");
+    ///
+    /// assert_eq!(index.len_chars(), 4);
+    /// assert_eq!(index.normalized(), "föo");
+    /// ```
+    #[cfg(feature = "unicode-normalization")]
+    #[must_use]
+    pub fn normalized(&self) -> Cow<'a, str> {
+        normalize_nfc(self.buf)
+    }
+
+    /// Converts a byte index into the index of the char that starts at that byte in the backing string.
+    ///
+    /// Returns `None` if the byte index is out of bounds or is not on a char boundary.
+    /// This is the inverse of [`char_to_byte`][IndexedChars::char_to_byte], and performs a binary search over the index,
+    /// with the exception of ascii strings where it is O(1).
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::IndexedChars;
+    /// let index = IndexedChars::new("föo");
+    ///
+    /// assert_eq!(index.byte_to_char(3), Some(2));
+    /// // 'ö' is 2 bytes long, so byte 2 is in the middle of it
+    /// assert_eq!(index.byte_to_char(2), None);
+    /// assert_eq!(index.byte_to_char(4), None);
+    /// ```
+    #[must_use]
+    pub fn byte_to_char(&self, byte_index: usize) -> Option<usize> {
+        self.inner.byte_to_char(self.buf, byte_index)
+    }
+
+    /// Returns the number of chars in the substring spanning the bytes in `range`, without scanning it.
+    ///
+    /// This converts both ends with [`byte_to_char`][IndexedChars::byte_to_char] and is O(log n), the end may be the length of the string.
+    /// Returns `None` if the range is reversed, out of bounds, or either end does not lie on a char boundary.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::IndexedChars;
+    /// let index = IndexedChars::new("föobär");
+    ///
+    /// assert_eq!(index.char_count_in_range(1..7), Some(4));
+    /// assert_eq!(index.char_count_in_range(0..8), Some(6));
+    /// // byte 2 is in the middle of 'ö'
+    /// assert_eq!(index.char_count_in_range(2..7), None);
+    /// ```
+    #[must_use]
+    pub fn char_count_in_range(&self, range: Range<usize>) -> Option<usize> {
+        self.inner.char_count_in_range(self.buf, range)
+    }
+
+    /// Converts a char index into its zero based `(line, column)` position, where lines are separated by `\n` and columns count chars.
+    ///
+    /// The index one past the last char is accepted, as it is a valid cursor position, and `None` is returned if the index is out of bounds.
+    /// No line index is kept, lines are counted by scanning the bytes before the char, so this is O(n).
+    /// A `\r` before a `\n` is counted as the last column of its line.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::IndexedChars;
+    /// let index = IndexedChars::new("föo\nbär");
+    ///
+    /// assert_eq!(index.line_col(1), Some((0, 1)));
+    /// assert_eq!(index.line_col(5), Some((1, 1)));
+    /// assert_eq!(index.line_col(7), Some((1, 3)));
+    /// assert_eq!(index.line_col(8), None);
+    /// ```
+    #[must_use]
+    pub fn line_col(&self, char_index: usize) -> Option<(usize, usize)> {
+        self.inner.line_col(self.buf, char_index)
+    }
+
+    /// Returns the width of the backing string when displayed in a terminal, in columns, as the sum of the widths of its chars.
+    ///
+    /// Wide chars such as CJK ideographs take 2 columns, while combining and control chars take none.
+    /// Only available with the `unicode-width` feature enabled.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::IndexedChars;
+    /// let index = IndexedChars::new("a中文");
+    ///
+    /// assert_eq!(index.display_width(), 5);
+    /// // columns 1 and 2 are both covered by '中'
+    /// assert_eq!(index.width_to_char(2), Some(1));
+    /// assert_eq!(index.width_to_char(3), Some(2));
+    /// assert_eq!(index.width_to_char(5), Some(3));
+    /// ```
+    #[cfg(feature = "unicode-width")]
+    #[must_use]
+    pub fn display_width(&self) -> usize {
+        display_width(self.buf)
+    }
+
+    /// Converts a display column into the index of the char covering it, such as for placing a cursor at a terminal column.
+    ///
+    /// The column one past the last char is accepted, and returns the char count.
+    /// Returns `None` if the column is past the end of the string. This is O(n).
+    /// Only available with the `unicode-width` feature enabled.
+    #[cfg(feature = "unicode-width")]
+    #[must_use]
+    pub fn width_to_char(&self, column: usize) -> Option<usize> {
+        self.inner.width_to_char(self.buf, column)
+    }
+
+    /// Converts a char index into the index of its first UTF-16 code unit, for interop with UTF-16 based APIs.
+    ///
+    /// The index one past the last char is accepted, and returns the length of the string in UTF-16 code units.
+    /// Returns `None` if the char index is out of bounds.
+    ///
+    /// Chars at or above U+10000 take two code units, these are counted with a binary search if the index was built with
+    /// [`with_utf16_index`][IndexedChars::with_utf16_index], otherwise this is O(n) unless the string is ascii, but only scans bytes before the char.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::IndexedChars;
+    /// let index = IndexedChars::new("f💯o");
+    ///
+    /// assert_eq!(index.char_to_utf16(2), Some(3));
+    /// assert_eq!(index.utf16_to_char(3), Some(2));
+    /// assert_eq!(index.utf16_to_char(2), None);
+    /// ```
+    #[must_use]
+    pub fn char_to_utf16(&self, index: usize) -> Option<usize> {
+        self.inner.char_to_utf16(self.buf, index)
+    }
+
+    /// Converts a UTF-16 code unit index into a char index, for interop with UTF-16 based APIs.
+    ///
+    /// The index one past the last code unit is accepted, and returns the char count.
+    /// Returns `None` if the index is out of bounds or points to the second code unit of a surrogate pair.
+    /// This is O(log n) if the index was built with [`with_utf16_index`][IndexedChars::with_utf16_index], and O(n) otherwise unless the string is ascii.
+    #[must_use]
+    pub fn utf16_to_char(&self, utf16_index: usize) -> Option<usize> {
+        self.inner.utf16_to_char(self.buf, utf16_index)
+    }
+
+    /// Returns the char index of the first occurrence of `c` in the backing string, or `None` if it does not occur.
+    ///
+    /// This is [`str::find`] followed by converting the byte index it returns with [`byte_to_char`][IndexedChars::byte_to_char].
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::IndexedChars;
+    /// let index = IndexedChars::new("föoöa");
+    ///
+    /// assert_eq!(index.find_char('ö'), Some(1));
+    /// assert_eq!(index.rfind_char('ö'), Some(3));
+    /// assert_eq!(index.find_char('x'), None);
+    /// ```
+    #[must_use]
+    pub fn find_char(&self, c: char) -> Option<usize> {
+        // a match always starts on a char boundary, so this only returns None if there is no match
+        self.buf
+            .find(c)
+            .and_then(|byte_index| self.byte_to_char(byte_index))
+    }
+
+    /// Returns the char index of the last occurrence of `c` in the backing string, or `None` if it does not occur.
+    #[must_use]
+    pub fn rfind_char(&self, c: char) -> Option<usize> {
+        // a match always starts on a char boundary, so this only returns None if there is no match
+        self.buf
+            .rfind(c)
+            .and_then(|byte_index| self.byte_to_char(byte_index))
+    }
+
+    /// Returns the char index where `pat` first occurs in the backing string, or `None` if it does not occur.
+    ///
+    /// Like [`str::find`], an empty pattern always matches at index 0.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::IndexedChars;
+    /// let index = IndexedChars::new("föobär");
+    ///
+    /// assert_eq!(index.find_str("bär"), Some(3));
+    /// assert_eq!(index.find_str(""), Some(0));
+    /// assert_eq!(index.find_str("baz"), None);
+    /// ```
+    #[must_use]
+    pub fn find_str(&self, pat: &str) -> Option<usize> {
+        // handled separately as an empty string has no char at byte 0 for byte_to_char to find
+        if pat.is_empty() {
+            return Some(0);
+        }
+
+        self.buf
+            .find(pat)
+            .and_then(|byte_index| self.byte_to_char(byte_index))
+    }
+
+    /// Returns the number of occurrences of `c` in the backing string.
+    ///
+    /// Ascii chars are counted by scanning bytes, which is faster than [`str::matches`],
+    /// and non ascii chars are not searched for at all in an ascii string.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::IndexedChars;
+    /// let index = IndexedChars::new("föobär");
+    ///
+    /// assert_eq!(index.count_matches('o'), 1);
+    /// assert_eq!(index.count_matches('ö'), 1);
+    /// assert_eq!(index.count_matches('x'), 0);
+    /// ```
+    #[must_use]
+    pub fn count_matches(&self, c: char) -> usize {
+        self.inner.count_matches(self.buf, c)
+    }
+
+    /// Returns `true` if `c` occurs in the backing string.
+    ///
+    /// Ascii chars are searched for byte by byte, which is faster than [`str::contains`],
+    /// and non ascii chars are not searched for at all in an ascii string.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::IndexedChars;
+    /// let index = IndexedChars::new("föobär");
+    ///
+    /// assert!(index.contains_char('b'));
+    /// assert!(index.contains_char('ä'));
+    /// assert!(!index.contains_char('x'));
+    /// ```
+    #[must_use]
+    pub fn contains_char(&self, c: char) -> bool {
+        self.inner.contains_char(self.buf, c)
+    }
+
+    /// Creates a new [`IndexedChars`] over the chars in `range`, sharing the backing string of this one.
+    ///
+    /// The index of the new view is derived from this index rather than by rescanning the string,
+    /// locating the range is O(log n), and if the range contains non ascii chars its offsets are copied over in O(m) for a range of m chars.
+    ///
+    /// Returns `None` if the range is reversed or out of bounds.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::IndexedChars;
+    /// let index = IndexedChars::new("föobär");
+    ///
+    /// let sliced = index.char_slice(1..5).unwrap();
+    ///
+    /// assert_eq!(sliced.as_str(), "öobä");
+    /// assert_eq!(sliced.get_char(3), Some('ä'));
+    /// assert!(index.char_slice(4..7).is_none());
+    /// ```
+    #[must_use]
+    pub fn char_slice(&self, range: Range<usize>) -> Option<IndexedChars<'a, O>> {
+        let (bytes, inner) = self.inner.slice(self.buf, range)?;
+
+        Some(IndexedChars {
+            buf: &self.buf[bytes],
+            inner: InnerRef::Owned(inner),
+            // offsets derived from an unverified index are no more trustworthy
+            #[cfg(feature = "unsafe")]
+            verified: self.verified,
+        })
+    }
+
+    /// Same as [`char_slice`][IndexedChars::char_slice], but returns why the range could not be sliced instead of `None`.
+    ///
+    /// # Errors
+    /// Returns [`CharIndexError::InvalidRange`] if the range is reversed, and [`CharIndexError::OutOfBounds`] if it reaches past the last char.
+    pub fn try_char_slice(
+        &self,
+        range: Range<usize>,
+    ) -> Result<IndexedChars<'a, O>, CharIndexError> {
+        self.inner.check_char_range(self.buf, &range)?;
+
+        self.char_slice(range).ok_or(CharIndexError::OutOfBounds)
+    }
+
+    /// Creates a new [`IndexedChars`] over the substring at `byte_range`, sharing the backing string of this one.
+    ///
+    /// This is the same as [`char_slice`][IndexedChars::char_slice], but takes a byte range such as one found by searching the string,
+    /// the index of the new view is derived from this index rather than by rescanning the substring.
+    ///
+    /// Returns `None` if the range is reversed, out of bounds, or either end is not on a char boundary.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::IndexedChars;
+    /// let index = IndexedChars::new("föobär");
+    ///
+    /// let start = index.find("bär").unwrap();
+    /// let sub = index.subindex(start..index.len()).unwrap();
+    ///
+    /// assert_eq!(sub, "bär");
+    /// assert_eq!(sub.get_char(1), Some('ä'));
+    /// assert!(index.subindex(2..4).is_none());
+    /// ```
+    #[must_use]
+    pub fn subindex(&self, byte_range: Range<usize>) -> Option<IndexedChars<'a, O>> {
+        let start = self
+            .inner
+            .byte_to_char_boundary(self.buf, byte_range.start)?;
+        let end = self.inner.byte_to_char_boundary(self.buf, byte_range.end)?;
+
+        self.char_slice(start..end)
+    }
+
+    /// Same as [`subindex`][IndexedChars::subindex], but returns why the range could not be sliced instead of `None`.
+    ///
+    /// # Errors
+    /// Returns [`CharIndexError::InvalidRange`] if the range is reversed, [`CharIndexError::OutOfBounds`] if it reaches past the end of the string,
+    /// and [`CharIndexError::NotCharBoundary`] if either end is not on a char boundary.
+    pub fn try_subindex(
+        &self,
+        byte_range: Range<usize>,
+    ) -> Result<IndexedChars<'a, O>, CharIndexError> {
+        if byte_range.start > byte_range.end {
+            return Err(CharIndexError::InvalidRange);
+        }
+
+        if byte_range.end > self.buf.len() {
+            return Err(CharIndexError::OutOfBounds);
+        }
+
+        self.subindex(byte_range)
+            .ok_or(CharIndexError::NotCharBoundary)
+    }
+
+    /// Returns a view of this string with leading and trailing whitespace removed, as with [`str::trim`], keeping it indexed.
+    ///
+    /// The view is created as with [`subindex`][IndexedChars::subindex], so no rescan of the string is needed.
+    /// This is not named `trim` so as to not shadow [`str::trim`], which stays available through `Deref`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::IndexedChars;
+    /// let index = IndexedChars::new(" föo bär\n");
+    /// let trimmed = index.trim_indexed();
+    ///
+    /// assert_eq!(trimmed, "föo bär");
+    /// assert_eq!(trimmed.get_char(5), Some('ä'));
+    /// ```
+    #[must_use]
+    pub fn trim_indexed(&self) -> IndexedChars<'a, O> {
+        let end = self.buf.trim_end().len();
+        let start = end - self.buf[..end].trim_start().len();
+
+        self.trusted_subindex(start..end)
+    }
+
+    /// Same as [`trim_indexed`][IndexedChars::trim_indexed], but only removes leading whitespace, as with [`str::trim_start`].
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::IndexedChars;
+    /// let index = IndexedChars::new(" föo bär\n");
+    /// let trimmed = index.trim_start_indexed();
+    ///
+    /// assert_eq!(trimmed, "föo bär\n");
+    /// assert_eq!(trimmed.get_char(1), Some('ö'));
+    /// ```
+    #[must_use]
+    pub fn trim_start_indexed(&self) -> IndexedChars<'a, O> {
+        let start = self.buf.len() - self.buf.trim_start().len();
+
+        self.trusted_subindex(start..self.buf.len())
+    }
+
+    /// Same as [`trim_indexed`][IndexedChars::trim_indexed], but only removes trailing whitespace, as with [`str::trim_end`].
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::IndexedChars;
+    /// let index = IndexedChars::new(" föo bär\n");
+    /// let trimmed = index.trim_end_indexed();
+    ///
+    /// assert_eq!(trimmed, " föo bär");
+    /// assert_eq!(trimmed.get_char(6), Some('ä'));
+    /// ```
+    #[must_use]
+    pub fn trim_end_indexed(&self) -> IndexedChars<'a, O> {
+        let end = self.buf.trim_end().len();
+
+        self.trusted_subindex(0..end)
+    }
+
+    /// Same as [`subindex`][IndexedChars::subindex], for byte ranges known to be valid.
+    fn trusted_subindex(&self, byte_range: Range<usize>) -> IndexedChars<'a, O> {
+        // unwrap safe as callers only pass ranges found by trimming whole chars off the backing string
+        self.subindex(byte_range).unwrap()
+    }
+
+    /// Copies the backing string into an [`OwnedIndexedChars`], cloning the index rather than rebuilding it.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::IndexedChars;
+    /// let index = IndexedChars::new("föo");
+    /// let owned = index.to_owned_indexed();
+    ///
+    /// assert_eq!(owned, index);
+    /// assert_eq!(owned.get_char(1), Some('ö'));
+    /// ```
+    #[must_use]
+    pub fn to_owned_indexed(&self) -> OwnedIndexedChars<O> {
+        // an owned index is always trusted, so an unverified one is rebuilt instead
+        #[cfg(feature = "unsafe")]
+        if !self.verified {
+            return OwnedIndexedChars::new_with_width(String::from(self.buf));
+        }
+
+        OwnedIndexedChars::from_inner(String::from(self.buf), (*self.inner).clone())
+    }
+
+    /// Gets the substring of the backing string spanning the chars in `range`.
+    ///
+    /// An end bound equal to [`char_count`][IndexedChars::char_count] slices to the end of the string, and empty ranges return `Some("")`.
+    /// Returns `None` if the range is reversed or out of bounds.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::IndexedChars;
+    /// let index = IndexedChars::new("föobär");
+    ///
+    /// assert_eq!(index.get_str(1..4), Some("öob"));
+    /// assert_eq!(index.get_str(3..6), Some("bär"));
+    /// assert_eq!(index.get_str(2..2), Some(""));
+    /// assert_eq!(index.get_str(3..7), None);
+    /// ```
+    #[must_use]
+    pub fn get_str(&self, range: Range<usize>) -> Option<&'a str> {
+        self.inner.get_str(self.buf, range)
+    }
+
+    /// Converts a range of chars into the range of bytes spanning them in the backing string, for slicing its bytes by char bounds.
+    ///
+    /// This is O(log n) at worst, as with [`get_str`][IndexedChars::get_str], returns `None` if the range is reversed or out of bounds.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::IndexedChars;
+    /// let index = IndexedChars::new("föo");
+    ///
+    /// let bytes = index.char_to_byte_range(1..3).unwrap();
+    ///
+    /// assert_eq!(bytes, 1..4);
+    /// assert_eq!(&index.as_bytes()[bytes], "öo".as_bytes());
+    /// assert_eq!(index.char_to_byte_range(3..3), Some(index.byte_len()..index.byte_len()));
+    /// assert_eq!(index.char_to_byte_range(2..4), None);
+    /// ```
+    #[must_use]
+    pub fn char_to_byte_range(&self, char_range: Range<usize>) -> Option<Range<usize>> {
+        self.inner.char_to_byte_range(self.buf, char_range)
+    }
+
+    /// Same as [`get_str`][IndexedChars::get_str], but returns why the range could not be looked up instead of `None`.
+    ///
+    /// # Errors
+    /// Returns [`CharIndexError::InvalidRange`] if the range is reversed, and [`CharIndexError::OutOfBounds`] if it reaches past the last char.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::{CharIndexError, IndexedChars};
+    /// let index = IndexedChars::new("föo");
+    ///
+    /// assert_eq!(index.try_get_str(1..3), Ok("öo"));
+    /// assert_eq!(index.try_get_str(1..4), Err(CharIndexError::OutOfBounds));
+    /// ```
+    pub fn try_get_str(&self, range: Range<usize>) -> Result<&'a str, CharIndexError> {
+        self.inner.check_char_range(self.buf, &range)?;
 
-impl<'a> IndexedChars<'a> {
-    /// Constructs a new [`IndexedChars`] instance from a [`&str`]. This is O(n), but the cost should only be paid once ideally.
+        self.get_str(range).ok_or(CharIndexError::OutOfBounds)
+    }
+
+    /// Same as [`char_to_byte_range`][IndexedChars::char_to_byte_range], but returns why the range could not be looked up instead of `None`.
+    ///
+    /// # Errors
+    /// Returns [`CharIndexError::InvalidRange`] if the range is reversed, and [`CharIndexError::OutOfBounds`] if it reaches past the last char.
+    pub fn try_char_to_byte_range(
+        &self,
+        char_range: Range<usize>,
+    ) -> Result<Range<usize>, CharIndexError> {
+        self.inner.check_char_range(self.buf, &char_range)?;
+
+        self.char_to_byte_range(char_range)
+            .ok_or(CharIndexError::OutOfBounds)
+    }
+
+    /// Returns the length of the backing string in bytes, this is the same as [`str::len`].
+    #[must_use]
+    pub fn byte_len(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// Looks up a char or a substring by char index, depending on the type of `index`, like [`slice::get`].
     ///
+    /// A `usize` retrieves the char at that index like [`get_char`][IndexedChars::get_char],
+    /// and a range retrieves the substring spanning those chars like [`get_str`][IndexedChars::get_str], see [`CharIndex`].
+    /// Note that `str::get`, reachable through `Deref`, takes a range of bytes instead.
     ///
     /// # Examples
     /// ```rust
     /// # use char_index::IndexedChars;
-    /// let index = IndexedChars::new("foo");
-    /// # assert_eq!(index.get_char(0), Some('f'));
+    /// let index = IndexedChars::new("föo");
+    ///
+    /// assert_eq!(index.get_at(1), Some('ö'));
+    /// assert_eq!(index.get_at(1..3), Some("öo"));
+    /// assert_eq!(index.get_at(..=1), Some("fö"));
+    /// assert_eq!(index.get_at(4..), None);
     /// ```
     #[must_use]
-    pub fn new(s: &'a str) -> Self {
-        let inner = IndexedCharsInner::new(s);
+    pub fn get_at<I: CharIndex<'a>>(&self, index: I) -> Option<I::Output> {
+        index.get_in(self)
+    }
+
+    /// Returns a value that displays the chars in `range` without allocating, for logging part of a large string.
+    ///
+    /// Unlike [`get_str`][IndexedChars::get_str], this never fails, the range is truncated to the end of the string instead,
+    /// and a reversed range displays nothing.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::IndexedChars;
+    /// let index = IndexedChars::new("föbär");
+    ///
+    /// assert_eq!(format!("[{}]", index.display_range(1..3)), "[öb]");
+    /// assert_eq!(format!("[{}]", index.display_range(3..10)), "[är]");
+    /// assert_eq!(format!("[{}]", index.display_range(10..20)), "[]");
+    /// ```
+    #[must_use]
+    pub fn display_range(&self, range: Range<usize>) -> impl fmt::Display + 'a {
+        let len = self.char_count();
+        let end = range.end.min(len);
+        let start = range.start.min(end);
 
-        Self { buf: s, inner }
+        self.get_str(start..end).unwrap_or_default()
     }
 
-    /// Indexes into the backing string to retrieve the nth codepoint.
+    /// Gets the substring of the backing string spanning the chars in `range`, like [`get_str`][IndexedChars::get_str] but panicking instead of returning `None`.
     ///
-    /// This operation has an average case of O(1), and a worst case of O(log n).
+    /// Note that indexing with `&index[range]` goes through [`Deref`] to `str`, and so takes a range of bytes, not chars.
+    ///
+    /// # Panics
+    /// Panics if the range is reversed or its end is greater than [`len_chars`][IndexedChars::len_chars].
     ///
     /// # Examples
     /// ```rust
     /// # use char_index::IndexedChars;
-    /// assert_eq!(IndexedChars::new("foobar").get_char(3), Some('b'));
+    /// let index = IndexedChars::new("föo");
+    ///
+    /// assert_eq!(index.index_chars(1..3), "öo");
+    /// // byte range, through Deref
+    /// assert_eq!(&index[1..3], "ö");
     /// ```
     #[must_use]
-    pub fn get_char(&self, index: usize) -> Option<char> {
-        self.inner.get_char(self.buf, index)
+    #[track_caller]
+    pub fn index_chars(&self, range: Range<usize>) -> &'a str {
+        match self.get_str(range.clone()) {
+            Some(s) => s,
+            None if range.start > range.end => panic!(
+                "char range starts at {} but ends at {}",
+                range.start, range.end
+            ),
+            None => panic!(
+                "char range {}..{} out of bounds (len {})",
+                range.start,
+                range.end,
+                self.char_count()
+            ),
+        }
     }
 
     /// Returns the number of chars present in the backing string, this operation is free thanks to
@@ -65,6 +1186,365 @@ impl<'a> IndexedChars<'a> {
         self.inner.char_count(self.buf)
     }
 
+    /// Returns the number of chars (unicode scalar values) present in the backing string in O(1).
+    ///
+    /// This is the same as [`char_count`][IndexedChars::char_count], and is not to be confused with
+    /// [`str::len`] (reachable through `Deref`), which returns the length in bytes.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::IndexedChars;
+    /// let index = IndexedChars::new("föo");
+    ///
+    /// assert_eq!(index.len_chars(), 3);
+    /// assert_eq!(index.len(), 4);
+    /// ```
+    #[must_use]
+    pub fn len_chars(&self) -> usize {
+        self.char_count()
+    }
+
+    /// Returns `true` if the backing string is empty.
+    ///
+    /// A string with zero bytes has zero chars and vice versa,
+    /// so this is equivalent to both `len() == 0` and `len_chars() == 0`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::IndexedChars;
+    /// assert!(IndexedChars::new("").is_empty());
+    /// assert!(!IndexedChars::new("föo").is_empty());
+    /// ```
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.buf.is_empty()
+    }
+
+    /// Returns `true` if the ascii optimization is active, which is the case exactly when the backing string is pure ascii.
+    ///
+    /// While it is active the index stores no offsets at all, lookups are a true O(1), and [`memory_footprint`][IndexedChars::memory_footprint] is 0.
+    /// Unlike [`str::is_ascii`], which this shadows, this is O(1).
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::IndexedChars;
+    /// let index = IndexedChars::new("föo");
+    ///
+    /// assert!(!index.is_ascii());
+    /// assert!(index.get_str(0..1).unwrap().is_ascii());
+    /// ```
+    #[must_use]
+    pub fn is_ascii(&self) -> bool {
+        self.inner.is_ascii()
+    }
+
+    /// Returns the amount of rollovers that occurred while building the index.
+    ///
+    /// A rollover happens every time the non ascii chars of the string accumulate another 255 extra bytes,
+    /// and lookups binary search over them, so this is a diagnostic for how far towards O(log n) char lookups have drifted.
+    /// See the section [`How it Works`](index.html#how-it-works) for details.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::IndexedChars;
+    /// // every 'ö' is 1 byte longer than an ascii char
+    /// let s = "ö".repeat(300);
+    /// let index = IndexedChars::new(&s);
+    ///
+    /// assert_eq!(index.rollover_count(), 1);
+    /// ```
+    #[must_use]
+    pub fn rollover_count(&self) -> usize {
+        self.inner.rollover_count()
+    }
+
+    /// Returns the amount of heap memory in bytes used by the index, not counting the backing string.
+    ///
+    /// Ascii strings do not need an index at all, and so will always return 0 here.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::IndexedChars;
+    /// assert_eq!(IndexedChars::new("foo").memory_footprint(), 0);
+    /// assert_eq!(IndexedChars::new("föo").memory_footprint(), 3);
+    /// ```
+    #[must_use]
+    pub fn memory_footprint(&self) -> usize {
+        self.inner.memory_footprint()
+    }
+
+    /// Returns the amount of char offsets the index can hold without reallocating.
+    ///
+    /// This is 0 for ascii strings, which store no offsets.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::IndexedChars;
+    /// assert_eq!(IndexedChars::new("föo").capacity_chars(), 3);
+    /// assert_eq!(IndexedChars::new("foo").capacity_chars(), 0);
+    /// ```
+    #[must_use]
+    pub fn capacity_chars(&self) -> usize {
+        self.inner.capacity_chars()
+    }
+
+    /// Returns the amount of rollover points the index can hold without reallocating.
+    #[must_use]
+    pub fn capacity_rollovers(&self) -> usize {
+        self.inner.capacity_rollovers()
+    }
+
+    /// Returns an iterator over the chars of the backing string.
+    ///
+    /// This walks the string directly rather than going through the index, so a full traversal is O(n).
+    /// The returned iterator is double ended and knows its exact length.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::IndexedChars;
+    /// let index = IndexedChars::new("foobar");
+    ///
+    /// assert_eq!(index.chars().len(), 6);
+    /// assert_eq!(index.chars().rev().next(), Some('r'));
+    /// ```
+    #[must_use]
+    pub fn chars(&self) -> Chars<'a> {
+        Chars::new(self.buf, self.char_count())
+    }
+
+    /// Clears `out` and fills it with the chars of the backing string, reusing its allocation.
+    ///
+    /// Exactly enough space for the chars is reserved up front with the cached char count, so `out` grows at most once.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::IndexedChars;
+    /// let index = IndexedChars::new("föo");
+    /// let mut out = vec!['x'; 10];
+    ///
+    /// index.collect_chars_into(&mut out);
+    ///
+    /// assert_eq!(out, ['f', 'ö', 'o']);
+    /// assert!(out.capacity() >= 10);
+    /// ```
+    pub fn collect_chars_into(&self, out: &mut Vec<char>) {
+        out.clear();
+        out.reserve_exact(self.char_count());
+        out.extend(self.chars());
+    }
+
+    /// Returns an iterator over the chars of the backing string, starting at the char at `index`.
+    ///
+    /// The index is converted to a byte index once, then the rest of the string is walked, which is much cheaper than calling [`get_char`][IndexedChars::get_char] in a loop.
+    /// `index == len_chars` returns an empty iterator, and `None` is returned if `index` is greater than that.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::IndexedChars;
+    /// let index = IndexedChars::new("föobär");
+    ///
+    /// assert!(index.chars_from(3).unwrap().eq("bär".chars()));
+    /// assert_eq!(index.chars_from(6).unwrap().next(), None);
+    /// assert!(index.chars_from(7).is_none());
+    /// ```
+    #[must_use]
+    pub fn chars_from(&self, index: usize) -> Option<Chars<'a>> {
+        let byte_index = self.nth_char_boundary(index)?;
+
+        Some(Chars::new(
+            &self.buf[byte_index..],
+            self.char_count() - index,
+        ))
+    }
+
+    /// Returns an iterator over the chars of the backing string in reverse order, from last to first.
+    ///
+    /// This is equivalent to `chars().rev()`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::IndexedChars;
+    /// let index = IndexedChars::new("föo");
+    ///
+    /// assert!(index.rchars().eq(['o', 'ö', 'f']));
+    /// ```
+    pub fn rchars(&self) -> Rev<Chars<'a>> {
+        self.chars().rev()
+    }
+
+    /// Returns a new [`OwnedIndexedChars`] of the chars of the backing string in reverse order.
+    ///
+    /// The offsets of the reversed string differ from these ones, so they are computed as each char is pushed onto it,
+    /// which indexes it in the same pass that builds it.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::IndexedChars;
+    /// let index = IndexedChars::new("föobär");
+    /// let reversed = index.reversed();
+    ///
+    /// assert_eq!(reversed, "räboöf");
+    /// assert_eq!(reversed.get_char(1), Some('ä'));
+    /// ```
+    #[must_use]
+    pub fn reversed(&self) -> OwnedIndexedChars<O> {
+        self.rchars().collect()
+    }
+
+    /// Returns an iterator over the chars of the backing string and their positions.
+    ///
+    /// Items are yielded as `(char_index, byte_index, char)`, the char index matches what would be passed to [`get_char`][IndexedChars::get_char].
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::IndexedChars;
+    /// let index = IndexedChars::new("fö");
+    ///
+    /// let mut iter = index.char_indices();
+    ///
+    /// assert_eq!(iter.next(), Some((0, 0, 'f')));
+    /// assert_eq!(iter.next(), Some((1, 1, 'ö')));
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    #[must_use]
+    pub fn char_indices(&self) -> CharIndices<'a> {
+        CharIndices::new(self.buf, self.char_count())
+    }
+
+    /// Returns an iterator over the chars of the backing string and the byte index each starts at, in order.
+    ///
+    /// This is the same as [`char_indices`][IndexedChars::char_indices] without the char index, in the `(char, byte_start)` shape of a typical rendering loop.
+    /// No lookups are done, the string is walked directly.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::IndexedChars;
+    /// let index = IndexedChars::new("föo");
+    /// let mut positions = index.positions();
+    ///
+    /// assert_eq!(positions.len(), 3);
+    /// assert_eq!(positions.next(), Some(('f', 0)));
+    /// assert_eq!(positions.next(), Some(('ö', 1)));
+    /// assert_eq!(positions.next(), Some(('o', 3)));
+    /// ```
+    #[must_use]
+    pub fn positions(
+        &self,
+    ) -> impl ExactSizeIterator<Item = (char, usize)> + DoubleEndedIterator + 'a {
+        self.char_indices().map(|(_, byte_idx, c)| (c, byte_idx))
+    }
+
+    /// Returns an iterator over the chars of the backing string and their char indices in reverse order, from last to first.
+    ///
+    /// Items are yielded as `(char_index, char)` pairs for reverse parsing, with char indices counting down from `len_chars - 1`,
+    /// use [`char_indices`][IndexedChars::char_indices] and `rev` to also get byte indices.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::IndexedChars;
+    /// let index = IndexedChars::new("fö");
+    ///
+    /// let mut iter = index.rchar_indices();
+    ///
+    /// assert_eq!(iter.next(), Some((1, 'ö')));
+    /// assert_eq!(iter.next(), Some((0, 'f')));
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    #[must_use]
+    pub fn rchar_indices(
+        &self,
+    ) -> impl ExactSizeIterator<Item = (usize, char)> + DoubleEndedIterator + 'a {
+        self.char_indices()
+            .rev()
+            .map(|(char_idx, _, c)| (char_idx, c))
+    }
+
+    /// Returns an iterator over all overlapping windows of `size` chars in the backing string, as string slices.
+    ///
+    /// If the string has fewer than `size` chars, no windows are yielded.
+    /// Each step is O(1), as the window boundaries are slid forward rather than looked up in the index.
+    ///
+    /// # Panics
+    /// Panics if `size` is 0.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::IndexedChars;
+    /// let index = IndexedChars::new("föo");
+    ///
+    /// let mut iter = index.char_windows(2);
+    ///
+    /// assert_eq!(iter.next(), Some("fö"));
+    /// assert_eq!(iter.next(), Some("öo"));
+    /// assert_eq!(iter.next(), None);
+    /// assert_eq!(index.char_windows(4).next(), None);
+    /// ```
+    #[must_use]
+    pub fn char_windows(&self, size: usize) -> CharWindows<'a> {
+        assert!(size != 0, "window size must be non-zero");
+
+        CharWindows::new(
+            self.buf,
+            self.char_count(),
+            size,
+            self.nth_char_boundary(size),
+        )
+    }
+
+    /// Returns an iterator over non overlapping chunks of `size` chars in the backing string, as string slices.
+    ///
+    /// If the char count is not a multiple of `size`, the last chunk is shorter.
+    ///
+    /// # Panics
+    /// Panics if `size` is 0.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::IndexedChars;
+    /// let index = IndexedChars::new("föobär");
+    ///
+    /// let mut iter = index.char_chunks(2);
+    ///
+    /// assert_eq!(iter.next(), Some("fö"));
+    /// assert_eq!(iter.next(), Some("ob"));
+    /// assert_eq!(iter.next(), Some("är"));
+    /// assert_eq!(iter.next(), None);
+    /// assert_eq!(index.char_chunks(4).collect::<Vec<_>>(), ["föob", "är"]);
+    /// ```
+    #[must_use]
+    pub fn char_chunks(&self, size: usize) -> CharChunks<'a> {
+        assert!(size != 0, "chunk size must be non-zero");
+
+        CharChunks::new(self.buf, self.char_count(), size)
+    }
+
+    /// Returns an iterator over the substrings of the backing string separated by `pat`, each paired with the char index it starts at.
+    ///
+    /// Segments are split exactly like [`str::split`], so leading, trailing, and consecutive delimiters produce empty segments.
+    /// The char index is kept running from the lengths of the segments, so this is O(n) over the whole iteration.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::IndexedChars;
+    /// let index = IndexedChars::new("föo,,bär,");
+    /// let segments: Vec<_> = index.split_char_indices(',').collect();
+    ///
+    /// assert_eq!(segments, [(0, "föo"), (4, ""), (5, "bär"), (9, "")]);
+    /// assert_eq!(index.get_char(5), Some('b'));
+    /// ```
+    pub fn split_char_indices(&self, pat: char) -> impl Iterator<Item = (usize, &'a str)> + 'a {
+        let buf: &str = self.buf;
+        let mut char_idx = 0;
+
+        buf.split(pat).map(move |segment| {
+            let start = char_idx;
+            // skip over the segment and the delimiter after it
+            char_idx += segment.chars().count() + 1;
+
+            (start, segment)
+        })
+    }
+
     /// Returns a reference to the backing `&str`
     #[must_use]
     pub fn as_str(&self) -> &str {
@@ -74,7 +1554,7 @@ impl<'a> IndexedChars<'a> {
 
 // The following lines are all trait implementations made to mirror what str does, and be compatible with str
 
-impl Deref for IndexedChars<'_> {
+impl<O> Deref for IndexedChars<'_, O> {
     type Target = str;
 
     fn deref(&self) -> &str {
@@ -82,75 +1562,111 @@ impl Deref for IndexedChars<'_> {
     }
 }
 
-impl AsRef<str> for IndexedChars<'_> {
+impl<O> AsRef<str> for IndexedChars<'_, O> {
     fn as_ref(&self) -> &str {
         self
     }
 }
 
-impl Borrow<str> for IndexedChars<'_> {
+impl<O> AsRef<[u8]> for IndexedChars<'_, O> {
+    fn as_ref(&self) -> &[u8] {
+        self.buf.as_bytes()
+    }
+}
+
+impl<O> Borrow<str> for IndexedChars<'_, O> {
     fn borrow(&self) -> &str {
         self
     }
 }
 
-impl fmt::Debug for IndexedChars<'_> {
+impl<O> fmt::Debug for IndexedChars<'_, O> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         <&str as fmt::Debug>::fmt(&self.buf, f)
     }
 }
 
-impl fmt::Display for IndexedChars<'_> {
+impl<O> fmt::Display for IndexedChars<'_, O> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         <&str as fmt::Display>::fmt(&self.buf, f)
     }
 }
 
-impl Eq for IndexedChars<'_> {}
+impl<O> Eq for IndexedChars<'_, O> {}
 
-impl PartialEq for IndexedChars<'_> {
+impl<O> PartialEq for IndexedChars<'_, O> {
     fn eq(&self, other: &Self) -> bool {
-        self.buf.eq(other.buf)
+        self.inner.same_char_count(&other.inner) && self.buf.eq(other.buf)
     }
 }
 
-impl PartialEq<str> for IndexedChars<'_> {
+impl<O> PartialEq<str> for IndexedChars<'_, O> {
     fn eq(&self, other: &str) -> bool {
         self.buf.eq(other)
     }
 }
 
-impl PartialEq<IndexedChars<'_>> for str {
-    fn eq(&self, other: &IndexedChars<'_>) -> bool {
+impl<O> PartialEq<IndexedChars<'_, O>> for str {
+    fn eq(&self, other: &IndexedChars<'_, O>) -> bool {
         self.eq(other.buf)
     }
 }
 
-impl Ord for IndexedChars<'_> {
+impl<O> PartialEq<&str> for IndexedChars<'_, O> {
+    fn eq(&self, other: &&str) -> bool {
+        self.buf.eq(*other)
+    }
+}
+
+impl<O> PartialEq<IndexedChars<'_, O>> for &str {
+    fn eq(&self, other: &IndexedChars<'_, O>) -> bool {
+        (*self).eq(other.buf)
+    }
+}
+
+impl<O> PartialEq<String> for IndexedChars<'_, O> {
+    fn eq(&self, other: &String) -> bool {
+        self.buf.eq(other.as_str())
+    }
+}
+
+impl<O> PartialEq<IndexedChars<'_, O>> for String {
+    fn eq(&self, other: &IndexedChars<'_, O>) -> bool {
+        self.as_str().eq(other.buf)
+    }
+}
+
+impl<O> Ord for IndexedChars<'_, O> {
     fn cmp(&self, other: &Self) -> Ordering {
         self.buf.cmp(other.buf)
     }
 }
 
-impl PartialOrd for IndexedChars<'_> {
+impl<O> PartialOrd for IndexedChars<'_, O> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
     }
 }
 
-impl PartialOrd<str> for IndexedChars<'_> {
+impl<O> PartialOrd<str> for IndexedChars<'_, O> {
     fn partial_cmp(&self, other: &str) -> Option<Ordering> {
         Some((*self.buf).cmp(other))
     }
 }
 
-impl PartialOrd<IndexedChars<'_>> for str {
-    fn partial_cmp(&self, other: &IndexedChars<'_>) -> Option<Ordering> {
+impl<O> PartialOrd<IndexedChars<'_, O>> for str {
+    fn partial_cmp(&self, other: &IndexedChars<'_, O>) -> Option<Ordering> {
         Some(self.cmp(other.buf))
     }
 }
 
-impl Hash for IndexedChars<'_> {
+impl<O> From<IndexedChars<'_, O>> for String {
+    fn from(index: IndexedChars<'_, O>) -> Self {
+        String::from(index.buf)
+    }
+}
+
+impl<O> Hash for IndexedChars<'_, O> {
     fn hash<H: Hasher>(&self, state: &mut H) {
         self.buf.hash(state);
     }
@@ -0,0 +1,118 @@
+//! Module containing [`IndexBuilder`], for indexing a string that arrives in chunks
+
+use alloc::string::String;
+
+use crate::{offset::OffsetInt, OwnedIndexedChars};
+
+/// A builder that indexes a string as chunks of it are pushed, such as text read from a file or the network.
+///
+/// Every chunk extends the index in place like [`OwnedIndexedChars::push_str`], only scanning that chunk,
+/// so [`build`][IndexBuilder::build] does not rescan the string.
+/// The string stays in the ascii niche until the first non ascii char arrives, whichever chunk it is in.
+///
+/// This is the same as [`IndexedWriter`](crate::IndexedWriter), for callers that have string chunks rather than format arguments.
+///
+/// # Examples
+/// ```rust
+/// # use char_index::IndexBuilder;
+/// let mut builder = IndexBuilder::new();
+///
+/// for chunk in ["foo", "bär", "baz"] {
+///     builder.push_chunk(chunk);
+/// }
+///
+/// let index = builder.build();
+///
+/// assert_eq!(index, "foobärbaz");
+/// assert_eq!(index.get_char(6), Some('b'));
+/// ```
+#[derive(Debug, Clone)]
+pub struct IndexBuilder<O = u8> {
+    /// String pushed so far, indexed as it grows
+    buf: OwnedIndexedChars<O>,
+}
+
+impl IndexBuilder {
+    /// Constructs a new empty [`IndexBuilder`].
+    #[must_use]
+    pub fn new() -> Self {
+        Self::new_with_width()
+    }
+}
+
+impl<O: OffsetInt> IndexBuilder<O> {
+    /// Constructs a new empty [`IndexBuilder`], storing its offsets in `O`.
+    ///
+    /// This is the same as [`new`][IndexBuilder::new] for any offset width.
+    #[must_use]
+    pub fn new_with_width() -> Self {
+        Self {
+            buf: OwnedIndexedChars::new_with_width(String::new()),
+        }
+    }
+
+    /// Appends a chunk to the end of the string, indexing only the chunk.
+    pub fn push_chunk(&mut self, chunk: &str) {
+        self.buf.push_str(chunk);
+    }
+
+    /// Returns the string pushed so far.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.buf
+    }
+
+    /// Returns the number of chars pushed so far in O(1).
+    #[must_use]
+    pub fn char_count(&self) -> usize {
+        self.buf.char_count()
+    }
+
+    /// Consumes the builder, returning the pushed string and its already built index.
+    #[must_use]
+    pub fn build(self) -> OwnedIndexedChars<O> {
+        self.buf
+    }
+}
+
+impl<O: OffsetInt> Default for IndexBuilder<O> {
+    fn default() -> Self {
+        Self::new_with_width()
+    }
+}
+
+#[test]
+fn builder() {
+    use alloc::vec::Vec;
+
+    fn check(chunks: &[&str]) {
+        let mut builder = IndexBuilder::<u8>::new_with_width();
+
+        for chunk in chunks {
+            builder.push_chunk(chunk);
+        }
+
+        let expected: String = chunks.concat();
+        let index = builder.build();
+        let fresh = OwnedIndexedChars::new(expected.clone());
+
+        assert!(index.validate());
+        assert_eq!(index, expected);
+        assert_eq!(index.is_ascii(), fresh.is_ascii());
+        assert_eq!(index.rollover_count(), fresh.rollover_count());
+        assert!(index.char_offsets().eq(fresh.char_offsets()));
+    }
+
+    check(&[]);
+    check(&["foo", "", "bar"]);
+    // the first non ascii char only arrives in a later chunk
+    check(&["foo", "bar", "bär", "baz"]);
+    check(&["foo", "💯"]);
+
+    // non ascii chunks interleaved with long ascii ones, rolling over across chunk boundaries
+    let ascii = "foobar".repeat(50);
+    let chunks: Vec<&str> = (0..100)
+        .map(|i| if i % 3 == 0 { "föö💯" } else { &*ascii })
+        .collect();
+    check(&chunks);
+}
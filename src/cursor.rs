@@ -0,0 +1,76 @@
+//! Module containing [`CharCursor`], a cursor for looking up chars at nearby indices
+
+use crate::{offset::OffsetInt, IndexedCharsInner};
+
+/// A cursor over an indexed string, which makes looking up chars near the previously looked up one cheaper.
+///
+/// Each lookup with `get_char` binary searches the rollovers of the index, while a cursor remembers where it was in them,
+/// so moving to a nearby index is O(1), which makes sequential access faster on strings with many rollovers.
+/// Jumping far away falls back to a binary search, so any access pattern is at worst as fast as `get_char`.
+///
+/// This struct is created by the `cursor` method on [`IndexedChars`](crate::IndexedChars) and [`OwnedIndexedChars`](crate::OwnedIndexedChars).
+///
+/// # Examples
+/// ```rust
+/// # use char_index::IndexedChars;
+/// let index = IndexedChars::new("föobär");
+/// let mut cursor = index.cursor();
+///
+/// assert_eq!(cursor.advance_to(4), Some('ä'));
+/// assert_eq!(cursor.advance_to(1), Some('ö'));
+/// assert_eq!(cursor.position(), 1);
+/// assert_eq!(cursor.byte_position(), 1);
+/// ```
+#[derive(Debug, Clone)]
+pub struct CharCursor<'a, O = u8> {
+    /// Backing string buffer
+    buf: &'a str,
+    /// Char offsets index of the backing string
+    inner: &'a IndexedCharsInner<O>,
+    /// Char index the cursor is at
+    index: usize,
+    /// Byte index the cursor is at
+    byte_index: usize,
+    /// Amount of rollovers that apply to the char index the cursor is at
+    rollover: usize,
+}
+
+impl<'a, O: OffsetInt> CharCursor<'a, O> {
+    /// Creates a new [`CharCursor`] at the start of a string, the index passed must be the one built for it.
+    pub(crate) fn new(buf: &'a str, inner: &'a IndexedCharsInner<O>) -> Self {
+        Self {
+            buf,
+            inner,
+            index: 0,
+            byte_index: 0,
+            rollover: 0,
+        }
+    }
+
+    /// Moves the cursor to the char at `index`, and returns that char.
+    ///
+    /// If the index is out of bounds `None` is returned and the cursor does not move.
+    /// This is O(1) when `index` is close to the current position, and O(log n) otherwise.
+    pub fn advance_to(&mut self, index: usize) -> Option<char> {
+        let byte_index = self
+            .inner
+            .char_to_byte_hinted(self.buf, index, &mut self.rollover)?;
+
+        self.index = index;
+        self.byte_index = byte_index;
+
+        self.buf[byte_index..].chars().next()
+    }
+
+    /// Returns the char index the cursor is at.
+    #[must_use]
+    pub fn position(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the byte index in the backing string the cursor is at.
+    #[must_use]
+    pub fn byte_position(&self) -> usize {
+        self.byte_index
+    }
+}
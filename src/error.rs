@@ -0,0 +1,67 @@
+//! Module containing [`CharIndexError`], the error returned by the `try_` variants of lookups.
+
+use core::fmt;
+
+/// The reason a range or index could not be looked up in an indexed string.
+///
+/// This is returned by the `try_` variants of methods such as [`get_str`](crate::IndexedChars::get_str),
+/// which otherwise return `None` without saying why.
+///
+/// Implements `std::error::Error` with the `std` feature enabled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum CharIndexError {
+    /// The index or range reaches past the end of the string
+    OutOfBounds,
+    /// A byte index does not lie on a char boundary
+    NotCharBoundary,
+    /// The range is reversed, its start is past its end
+    InvalidRange,
+}
+
+impl fmt::Display for CharIndexError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            CharIndexError::OutOfBounds => "index out of bounds",
+            CharIndexError::NotCharBoundary => "byte index is not a char boundary",
+            CharIndexError::InvalidRange => "range start is greater than its end",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CharIndexError {}
+
+#[test]
+fn error() {
+    use crate::{IndexedChars, OwnedIndexedChars};
+    use alloc::{string::String, string::ToString};
+
+    let index = IndexedChars::new("föobär");
+
+    assert_eq!(index.try_get_str(1..4), Ok("öob"));
+    assert_eq!(index.try_get_str(1..7), Err(CharIndexError::OutOfBounds));
+    assert_eq!(
+        index.try_get_str(core::ops::Range { start: 4, end: 1 }),
+        Err(CharIndexError::InvalidRange)
+    );
+    assert_eq!(index.try_char_to_byte_range(6..6), Ok(8..8));
+
+    assert_eq!(index.try_char_slice(3..6).unwrap(), "bär");
+    assert_eq!(index.try_char_slice(3..9), Err(CharIndexError::OutOfBounds));
+
+    assert_eq!(index.try_subindex(4..8).unwrap(), "bär");
+    assert_eq!(
+        index.try_subindex(2..8),
+        Err(CharIndexError::NotCharBoundary)
+    );
+    assert_eq!(index.try_subindex(4..9), Err(CharIndexError::OutOfBounds));
+
+    let owned = OwnedIndexedChars::new(String::from("föobär"));
+    assert_eq!(owned.try_get_str(0..2), Ok("fö"));
+
+    assert_eq!(
+        CharIndexError::NotCharBoundary.to_string(),
+        "byte index is not a char boundary"
+    );
+}
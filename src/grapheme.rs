@@ -0,0 +1,188 @@
+//! Module containing [`GraphemeIndexedChars`] and its trait implementations
+
+use core::{
+    borrow::Borrow,
+    cmp::{Eq, PartialEq},
+    fmt,
+    hash::{Hash, Hasher},
+    ops::{Deref, Range},
+};
+
+use crate::{offset::OffsetInt, IndexedCharsInner};
+
+/// A string whose extended grapheme cluster boundaries have been cached for ~O(1) cluster lookup.
+///
+/// This is the same as [`IndexedChars`](crate::IndexedChars), but indexes user perceived characters as defined by
+/// [`unicode-segmentation`](https://docs.rs/unicode-segmentation), so a letter followed by combining marks,
+/// or an emoji zwj sequence, is a single index rather than several.
+/// This is usually what cursor movement and other user facing operations want.
+///
+/// Clusters are stored with the same offset encoding as chars, but as they can be far longer than a char,
+/// strings with many long clusters will roll over more often than [`IndexedChars`](crate::IndexedChars) would.
+///
+/// Only available with the `unicode-segmentation` feature enabled.
+///
+/// This type mimics a `&'a str` with its trait impls, including `Debug`, `Display`, `PartialEq` with `&str`, `Hash`, and `AsRef`/`Borrow`.
+pub struct GraphemeIndexedChars<'a, O = u8> {
+    /// Backing string buffer
+    buf: &'a str,
+    /// Grapheme cluster offsets index
+    inner: IndexedCharsInner<O>,
+}
+
+impl<'a> GraphemeIndexedChars<'a> {
+    /// Constructs a new [`GraphemeIndexedChars`] instance from a [`&str`]. This is O(n), but the cost should only be paid once ideally.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::GraphemeIndexedChars;
+    /// let index = GraphemeIndexedChars::new("e\u{301}a");
+    ///
+    /// assert_eq!(index.get_grapheme(0), Some("e\u{301}"));
+    /// assert_eq!(index.get_grapheme(1), Some("a"));
+    /// ```
+    #[must_use]
+    pub fn new(s: &'a str) -> Self {
+        Self::new_with_width(s)
+    }
+}
+
+impl<'a, O: OffsetInt> GraphemeIndexedChars<'a, O> {
+    /// Constructs a new [`GraphemeIndexedChars`] instance from a [`&str`], storing its offsets in `O`.
+    ///
+    /// This is the same as [`new`][GraphemeIndexedChars::new] for any offset width,
+    /// wider offsets are worth it for strings made up of long clusters.
+    #[must_use]
+    pub fn new_with_width(s: &'a str) -> Self {
+        let inner = IndexedCharsInner::new_graphemes(s);
+
+        Self { buf: s, inner }
+    }
+
+    /// Indexes into the backing string to retrieve the nth extended grapheme cluster.
+    ///
+    /// This operation has an average case of O(1), and a worst case of O(log n).
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::GraphemeIndexedChars;
+    /// let index = GraphemeIndexedChars::new("a👩‍👩‍👧b");
+    ///
+    /// assert_eq!(index.get_grapheme(1), Some("👩‍👩‍👧"));
+    /// assert_eq!(index.get_grapheme(2), Some("b"));
+    /// assert_eq!(index.get_grapheme(3), None);
+    /// ```
+    #[must_use]
+    pub fn get_grapheme(&self, index: usize) -> Option<&'a str> {
+        let start = self.inner.char_to_byte(self.buf, index)?;
+        let end = self.inner.char_boundary(self.buf, index + 1)?;
+
+        Some(&self.buf[start..end])
+    }
+
+    /// Converts a grapheme cluster index into the byte index where that cluster starts in the backing string.
+    ///
+    /// Returns `None` if the index is out of bounds.
+    #[must_use]
+    pub fn grapheme_to_byte(&self, index: usize) -> Option<usize> {
+        self.inner.char_to_byte(self.buf, index)
+    }
+
+    /// Gets the substring of the backing string spanning the grapheme clusters in `range`.
+    ///
+    /// Returns `None` if the range is reversed or out of bounds.
+    #[must_use]
+    pub fn get_str(&self, range: Range<usize>) -> Option<&'a str> {
+        self.inner.get_str(self.buf, range)
+    }
+
+    /// Returns the number of extended grapheme clusters in the backing string in O(1).
+    #[must_use]
+    pub fn grapheme_count(&self) -> usize {
+        self.inner.char_count(self.buf)
+    }
+
+    /// Returns `true` if the backing string is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.buf.is_empty()
+    }
+
+    /// Returns the amount of rollovers that occurred while building the index.
+    #[must_use]
+    pub fn rollover_count(&self) -> usize {
+        self.inner.rollover_count()
+    }
+
+    /// Returns the amount of heap memory in bytes used by the index, not counting the backing string.
+    #[must_use]
+    pub fn memory_footprint(&self) -> usize {
+        self.inner.memory_footprint()
+    }
+
+    /// Returns a reference to the backing `&str`
+    #[must_use]
+    pub fn as_str(&self) -> &'a str {
+        self.buf
+    }
+}
+
+// The following lines are all trait implementations made to mirror what str does, and be compatible with str
+
+impl<O> Deref for GraphemeIndexedChars<'_, O> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.buf
+    }
+}
+
+impl<O> AsRef<str> for GraphemeIndexedChars<'_, O> {
+    fn as_ref(&self) -> &str {
+        self
+    }
+}
+
+impl<O> Borrow<str> for GraphemeIndexedChars<'_, O> {
+    fn borrow(&self) -> &str {
+        self
+    }
+}
+
+impl<O> fmt::Debug for GraphemeIndexedChars<'_, O> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        <str as fmt::Debug>::fmt(self.buf, f)
+    }
+}
+
+impl<O> fmt::Display for GraphemeIndexedChars<'_, O> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        <str as fmt::Display>::fmt(self.buf, f)
+    }
+}
+
+impl<O> Eq for GraphemeIndexedChars<'_, O> {}
+
+impl<O> PartialEq for GraphemeIndexedChars<'_, O> {
+    fn eq(&self, other: &Self) -> bool {
+        self.buf.eq(other.buf)
+    }
+}
+
+impl<O> PartialEq<str> for GraphemeIndexedChars<'_, O> {
+    fn eq(&self, other: &str) -> bool {
+        self.buf.eq(other)
+    }
+}
+
+impl<O> PartialEq<&str> for GraphemeIndexedChars<'_, O> {
+    fn eq(&self, other: &&str) -> bool {
+        self.buf.eq(*other)
+    }
+}
+
+impl<O> Hash for GraphemeIndexedChars<'_, O> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.buf.hash(state);
+    }
+}
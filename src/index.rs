@@ -0,0 +1,100 @@
+//! Module containing [`CharIndex`], the types that can index into an indexed string by char.
+
+use core::ops::{Range, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive};
+
+use crate::{offset::OffsetInt, IndexedChars};
+
+/// Private module housing the [`Sealed`](private::Sealed) supertrait of [`CharIndex`], this prevents implementations outside this crate.
+mod private {
+    /// Supertrait of [`CharIndex`](super::CharIndex).
+    pub trait Sealed {}
+}
+
+/// A type that can be used to look up chars or substrings by char index, with `get_at`.
+///
+/// This mirrors [`SliceIndex`](core::slice::SliceIndex), a `usize` retrieves a single `char`,
+/// while ranges of chars retrieve the `&str` spanning them, just as [`get_char`](IndexedChars::get_char) and [`get_str`](IndexedChars::get_str) do.
+///
+/// This trait is sealed, and is implemented for `usize` and every range of `usize`.
+pub trait CharIndex<'a>: private::Sealed {
+    /// The type returned when indexing with this type.
+    type Output;
+
+    /// Looks up this index in an indexed string, returning `None` if it is out of bounds.
+    #[doc(hidden)]
+    fn get_in<O: OffsetInt>(self, index: &IndexedChars<'a, O>) -> Option<Self::Output>;
+}
+
+impl private::Sealed for usize {}
+
+impl<'a> CharIndex<'a> for usize {
+    type Output = char;
+
+    fn get_in<O: OffsetInt>(self, index: &IndexedChars<'a, O>) -> Option<char> {
+        index.get_char(self)
+    }
+}
+
+impl private::Sealed for Range<usize> {}
+
+impl<'a> CharIndex<'a> for Range<usize> {
+    type Output = &'a str;
+
+    fn get_in<O: OffsetInt>(self, index: &IndexedChars<'a, O>) -> Option<&'a str> {
+        index.get_str(self)
+    }
+}
+
+impl private::Sealed for RangeInclusive<usize> {}
+
+impl<'a> CharIndex<'a> for RangeInclusive<usize> {
+    type Output = &'a str;
+
+    fn get_in<O: OffsetInt>(self, index: &IndexedChars<'a, O>) -> Option<&'a str> {
+        let end = self.end().checked_add(1)?;
+
+        index.get_str(*self.start()..end)
+    }
+}
+
+impl private::Sealed for RangeFrom<usize> {}
+
+impl<'a> CharIndex<'a> for RangeFrom<usize> {
+    type Output = &'a str;
+
+    fn get_in<O: OffsetInt>(self, index: &IndexedChars<'a, O>) -> Option<&'a str> {
+        index.get_str(self.start..index.char_count())
+    }
+}
+
+impl private::Sealed for RangeTo<usize> {}
+
+impl<'a> CharIndex<'a> for RangeTo<usize> {
+    type Output = &'a str;
+
+    fn get_in<O: OffsetInt>(self, index: &IndexedChars<'a, O>) -> Option<&'a str> {
+        index.get_str(0..self.end)
+    }
+}
+
+impl private::Sealed for RangeToInclusive<usize> {}
+
+impl<'a> CharIndex<'a> for RangeToInclusive<usize> {
+    type Output = &'a str;
+
+    fn get_in<O: OffsetInt>(self, index: &IndexedChars<'a, O>) -> Option<&'a str> {
+        let end = self.end.checked_add(1)?;
+
+        index.get_str(0..end)
+    }
+}
+
+impl private::Sealed for RangeFull {}
+
+impl<'a> CharIndex<'a> for RangeFull {
+    type Output = &'a str;
+
+    fn get_in<O: OffsetInt>(self, index: &IndexedChars<'a, O>) -> Option<&'a str> {
+        index.get_str(0..index.char_count())
+    }
+}
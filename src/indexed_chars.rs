@@ -1,183 +1,2630 @@
 //! Houses core implementation of char index.
 
 use alloc::vec::Vec;
+use core::{iter, mem, ops::Range};
+
+use crate::{offset::OffsetInt, CharIndexError};
 
 /// The core type of `char_index`.
 /// This struct implements building a memory efficient index of char
 ///  locations, and a method to access that index.
-#[derive(Debug)]
-pub(crate) struct IndexedCharsInner {
+#[derive(Debug, Clone)]
+pub(crate) struct IndexedCharsInner<O> {
     /// The char offsets, stores the amount that a given char index must increment by to be in the correct range
-    chars: Vec<u8>,
-    /// rollovers, stores the points where the offsets overflowed u8, so it may be binary searched to add `u8::MAX` * index_in_rollovers to the offset
+    chars: Vec<O>,
+    /// rollovers, stores the points where the offsets overflowed O, so it may be binary searched to add `O::MAX` * `index_in_rollovers` to the offset
+    ///
+    /// The invariant is that the byte index of char `i` is `i + chars[i] + O::MAX * r`, where `r` is the amount of rollovers `<= i`.
+    /// Stored offsets range over `0..=O::MAX`, so an offset of exactly `O::MAX` is stored as is, and only `O::MAX + 1` rolls over,
+    /// being stored as 1 with a rollover at its char index. Note that each rollover is worth `O::MAX` and not `O::MAX + 1`.
     rollovers: Vec<usize>,
+    /// The amount of chars in the indexed string, kept separately so it never has to be derived from the string
+    char_len: usize,
+    /// The char indices of every char above U+FFFF in order, as those take two UTF-16 code units,
+    /// only kept if a UTF-16 index was asked for with [`index_utf16`][Self::index_utf16]
+    astral: Option<Vec<usize>>,
 }
 
-impl IndexedCharsInner {
+impl<O: OffsetInt> IndexedCharsInner<O> {
     /// Computes a new char index from a backing string
     pub(crate) fn new(s: &str) -> Self {
+        let this = Self::new_in(s, Vec::new(), Vec::new());
+
+        // ensure we did not waste memory
+        debug_assert!(this.chars.capacity() == this.chars.len());
+
+        this
+    }
+
+    /// Computes a new char index from a backing string, storing it in the given allocations.
+    /// Any data already in the passed vectors is cleared, but their capacity is kept.
+    pub(crate) fn new_in(s: &str, chars: Vec<O>, rollovers: Vec<usize>) -> Self {
         // this is expensive but it lets us avoid big reallocs
         // it also lets us niche on ascii strings
-        // maybe-TODO(ultrabear) replace with no-std bytecount::num_chars?
-        let charlen = s.chars().count();
+        let charlen = count_chars(s);
+
+        Self::new_counted_in(s, chars, rollovers, charlen)
+    }
+
+    /// Computes a new char index from a backing string whose char count is already known, skipping the counting pass.
+    ///
+    /// A wrong count is caught by a debug assertion, in release builds the index is still built correctly.
+    pub(crate) fn new_with_char_count(s: &str, char_count: usize) -> Self {
+        debug_assert_eq!(char_count, count_chars(s), "wrong char count hint");
+
+        Self::new_counted_in(s, Vec::new(), Vec::new(), niche_char_count(s, char_count))
+    }
+
+    /// Computes a new char index from a backing string with `charlen` chars, storing it in the given allocations.
+    fn new_counted_in(
+        s: &str,
+        mut chars: Vec<O>,
+        mut rollovers: Vec<usize>,
+        charlen: usize,
+    ) -> Self {
+        chars.clear();
+        rollovers.clear();
+
+        let mut this = Self {
+            chars,
+            rollovers,
+            char_len: charlen,
+            astral: None,
+        };
 
         // if the number of chars is equal to the number of bytes we can skip allocating at all
         // this lets us niche on an ascii string
+        if charlen == s.len() {
+            return this;
+        }
+
+        // a string can not have more chars than bytes, so a wrong hint can not cause a huge reservation
+        this.chars.reserve_exact(charlen.min(s.len()));
+
+        for (char_idx, (real_idx, _)) in s.char_indices().enumerate() {
+            // every char takes at least one byte, so this never underflows
+            this.push_offset(char_idx, real_idx - char_idx);
+        }
+
+        // only differs from charlen if it came from a wrong hint
+        this.char_len = this.chars.len();
+
+        this
+    }
+
+    /// Computes a new index over the extended grapheme clusters of a string rather than its chars.
+    ///
+    /// Every other method treats the clusters as if they were chars, apart from [`byte_to_char`][Self::byte_to_char],
+    /// which relies on chars being at most 4 bytes long and must not be used on this index.
+    #[cfg(feature = "unicode-segmentation")]
+    pub(crate) fn new_graphemes(s: &str) -> Self {
+        use unicode_segmentation::UnicodeSegmentation;
+
+        let mut this = Self {
+            chars: Vec::new(),
+            rollovers: Vec::new(),
+            char_len: 0,
+            astral: None,
+        };
+
+        for (grapheme_idx, (byte_idx, _)) in s.grapheme_indices(true).enumerate() {
+            // stay in the ascii niche for as long as every cluster is a single byte
+            if this.is_ascii() {
+                if byte_idx == grapheme_idx {
+                    this.char_len += 1;
+                    continue;
+                }
+
+                this.chars.resize(grapheme_idx, O::from_usize(0));
+            }
+
+            this.push_offset(grapheme_idx, byte_idx - grapheme_idx);
+            this.char_len += 1;
+        }
+
+        // a multi byte last cluster does not move any start, but still leaves the niche
+        if this.is_ascii() && this.char_len != s.len() {
+            this.chars.resize(this.char_len, O::from_usize(0));
+        }
+
+        this.chars.shrink_to_fit();
+
+        this
+    }
+
+    /// Reassembles an index from its char offsets and rollovers, as returned by [`into_vecs`][Self::into_vecs].
+    ///
+    /// No validation is done, the parts must be exactly what [`new`][Self::new] would build for the string they are used with.
+    #[cfg(feature = "unsafe")]
+    pub(crate) fn from_vecs(chars: Vec<O>, rollovers: Vec<usize>, char_len: usize) -> Self {
+        Self {
+            chars,
+            rollovers,
+            char_len,
+            astral: None,
+        }
+    }
+
+    /// Takes apart this index, returning the allocations for its char offsets and rollovers.
+    pub(crate) fn into_vecs(self) -> (Vec<O>, Vec<usize>) {
+        (self.chars, self.rollovers)
+    }
+
+    /// Computes a new char index from a backing string, splitting the work across the rayon thread pool.
+    ///
+    /// The resulting index is identical to one made by [`new`][Self::new].
+    #[cfg(feature = "rayon")]
+    pub(crate) fn new_parallel(s: &str) -> Self {
+        /// Chunks smaller than this are not worth sending to another thread
+        const MIN_CHUNK_LEN: usize = 64 * 1024;
+
+        let chunk_len = (s.len() / rayon::current_num_threads()).max(MIN_CHUNK_LEN);
+
+        Self::new_chunked(s, chunk_len)
+    }
+
+    /// Computes a new char index by splitting the string into chunks of roughly `chunk_len` bytes and indexing them in parallel.
+    #[cfg(feature = "rayon")]
+    fn new_chunked(s: &str, chunk_len: usize) -> Self {
+        use alloc::vec;
+        use rayon::prelude::*;
+
+        let mut bounds = vec![0];
+        let mut last = 0;
+
+        while last < s.len() {
+            let mut next = (last + chunk_len).min(s.len());
+
+            while !s.is_char_boundary(next) {
+                next += 1;
+            }
+
+            bounds.push(next);
+            last = next;
+        }
+
+        let counts: Vec<usize> = bounds
+            .par_windows(2)
+            .map(|w| count_chars(&s[w[0]..w[1]]))
+            .collect();
+
+        let charlen = counts.iter().sum();
+
+        // same ascii niche as in new
         if charlen == s.len() {
             return Self {
                 chars: Vec::new(),
                 rollovers: Vec::new(),
+                char_len: charlen,
+                astral: None,
             };
         }
 
-        let mut chars = Vec::with_capacity(charlen);
-        let mut rollovers = Vec::new();
+        let mut chars = vec![O::from_usize(0); charlen];
 
-        for (char_idx, (real_idx, _)) in s.char_indices().enumerate() {
-            let mut offset_idx = real_idx - char_idx;
+        // hand out a disjoint part of chars to every chunk, alongside where that chunk starts in chars and in the string
+        let mut chunks = Vec::with_capacity(counts.len());
+        let mut rest = chars.as_mut_slice();
+        let mut char_start = 0;
+
+        for (w, &count) in bounds.windows(2).zip(&counts) {
+            let (head, tail) = core::mem::take(&mut rest).split_at_mut(count);
+
+            chunks.push((head, w[0], char_start));
+
+            rest = tail;
+            char_start += count;
+        }
+
+        let rollovers: Vec<Vec<usize>> = chunks
+            .into_par_iter()
+            .map(|(out, byte_start, char_start)| {
+                let mut rollovers = Vec::new();
+
+                // resume the rollover count from the last char of the previous chunk
+                let mut rollover_count = s[..byte_start]
+                    .char_indices()
+                    .next_back()
+                    .map_or(0, |(byte_idx, _)| {
+                        Self::rollovers_for_offset(byte_idx - (char_start - 1))
+                    });
+
+                for (i, ((byte_idx, _), slot)) in s[byte_start..]
+                    .char_indices()
+                    .zip(out.iter_mut())
+                    .enumerate()
+                {
+                    let char_idx = char_start + i;
+                    let offset = byte_start + byte_idx - char_idx;
+
+                    if Self::rollovers_for_offset(offset) > rollover_count {
+                        rollovers.push(char_idx);
+                        rollover_count += 1;
+                    }
+
+                    // rollovers_for_offset keeps the remainder within O::MAX
+                    *slot = O::from_usize(offset - rollover_count * O::MAX);
+                }
+
+                rollovers
+            })
+            .collect();
 
-            let u8_max = usize::from(u8::MAX);
+        Self {
+            chars,
+            rollovers: rollovers.concat(),
+            char_len: charlen,
+            astral: None,
+        }
+    }
+
+    /// Checks that this index is exactly the one [`new`][Self::new] would build for `buf`.
+    ///
+    /// This rebuilds the index from scratch, so it is O(n) and allocates.
+    pub(crate) fn validate(&self, buf: &str) -> bool {
+        let mut fresh = Self::new(buf);
+
+        if self.astral.is_some() {
+            fresh.index_utf16(buf);
+        }
+
+        self.chars == fresh.chars
+            && self.rollovers == fresh.rollovers
+            && self.char_len == fresh.char_len
+            && self.astral == fresh.astral
+    }
+
+    /// Builds the UTF-16 index for `buf`, the string this index was created with,
+    /// which makes [`char_to_utf16`][Self::char_to_utf16] and [`utf16_to_char`][Self::utf16_to_char] O(log n).
+    ///
+    /// The index is kept up to date by every method that modifies this one.
+    pub(crate) fn index_utf16(&mut self, buf: &str) {
+        let astral = buf
+            .chars()
+            .enumerate()
+            .filter(|&(_, c)| c.len_utf16() == 2)
+            .map(|(char_idx, _)| char_idx)
+            .collect();
+
+        self.astral = Some(astral);
+    }
+
+    /// Returns `true` if a UTF-16 index was built with [`index_utf16`][Self::index_utf16].
+    pub(crate) fn has_utf16_index(&self) -> bool {
+        self.astral.is_some()
+    }
+
+    /// Returns the amount of rollovers [`push_offset`][Self::push_offset] will have performed once it has pushed `offset`.
+    ///
+    /// Offsets grow by at most 3 per char, so a rollover happens exactly when the offset passes the next multiple of `O::MAX`,
+    /// which makes this a pure function of the offset.
+    #[cfg(feature = "rayon")]
+    fn rollovers_for_offset(offset: usize) -> usize {
+        offset.saturating_sub(1) / O::MAX
+    }
+
+    /// Extends the index with a char appended to the end of the string it was created with,
+    /// `buf` must be that string as it was before `c` was appended.
+    pub(crate) fn push_char(&mut self, buf: &str, c: char) {
+        self.push_str(buf, c.encode_utf8(&mut [0; 4]));
+    }
 
-            offset_idx -= rollovers.len() * u8_max;
+    /// Extends the index with a string appended to the end of the string it was created with,
+    /// `buf` must be that string as it was before `s` was appended.
+    pub(crate) fn push_str(&mut self, buf: &str, s: &str) {
+        self.push_str_at(buf.len(), s);
+    }
 
-            if offset_idx > u8_max {
-                rollovers.push(char_idx);
-                offset_idx -= u8_max;
+    /// Extends the index with a char appended to the end of a string of `byte_len` bytes, which this index was built for.
+    ///
+    /// This is [`push_char`][Self::push_char] for when that string is not available, such as while it is being rewritten in place.
+    pub(crate) fn push_char_at(&mut self, byte_len: usize, c: char) {
+        self.push_str_at(byte_len, c.encode_utf8(&mut [0; 4]));
+    }
 
-                debug_assert!(offset_idx <= u8_max);
+    /// Extends the index with a string appended to the end of a string of `byte_len` bytes, which this index was built for.
+    fn push_str_at(&mut self, byte_len: usize, s: &str) {
+        if self.is_ascii() {
+            if s.is_ascii() {
+                self.char_len += s.len();
+                return;
             }
 
-            // unwrap safe as chars cannot grow by more than 255 bytes at once
-            // and we just checked if it was over 255 bytes, conditionally subtracting
-            chars.push(offset_idx.try_into().unwrap());
+            debug_assert_eq!(self.char_len, byte_len);
+
+            // leaving the ascii niche, every char so far was one byte so their offsets are all zero
+            self.chars.resize(self.char_len, O::from_usize(0));
         }
 
-        // ensure we did not waste memory
-        debug_assert!(chars.capacity() == chars.len());
+        // resume from the last char, the offsets carry on from the end of the string
+        for (byte_idx, c) in s.char_indices() {
+            let char_idx = self.char_len;
+
+            self.push_offset(char_idx, byte_len + byte_idx - char_idx);
+            self.char_len += 1;
+
+            if let (Some(astral), 2) = (&mut self.astral, c.len_utf16()) {
+                astral.push(char_idx);
+            }
+        }
+    }
+
+    /// Empties the index, as if it was created for an empty string, while keeping its allocations.
+    pub(crate) fn clear(&mut self) {
+        self.truncate_to(0, 0);
+    }
+
+    /// Shortens the index to the first `char_len` chars of the string it was created with,
+    /// returning the byte length that string must be truncated to.
+    ///
+    /// Returns None and leaves the index untouched if `char_len` is not less than the char count.
+    pub(crate) fn truncate(&mut self, buf: &str, char_len: usize) -> Option<usize> {
+        let byte_len = self.char_to_byte(buf, char_len)?;
+
+        self.truncate_to(char_len, byte_len);
+
+        Some(byte_len)
+    }
+
+    /// Reindexes the string this index was created with from the char at `char_idx` onward, after it was modified from there.
+    ///
+    /// `buf` must be the modified string, where everything before `byte_idx` is unchanged,
+    /// and `byte_idx` must have been the byte index of the char at `char_idx` (or the string length) before the modification.
+    pub(crate) fn reindex_from(&mut self, buf: &str, char_idx: usize, byte_idx: usize) {
+        // the offsets before the modification are still correct, so only the rest is recomputed
+        self.truncate_to(char_idx, byte_idx);
+        self.push_str(&buf[..byte_idx], &buf[byte_idx..]);
+    }
+
+    /// Shortens the index to its first `char_len` chars, which must take up `byte_len` bytes of the string.
+    fn truncate_to(&mut self, char_len: usize, byte_len: usize) {
+        if byte_len == char_len {
+            // the remaining prefix is all ascii, so we can go back to the ascii niche
+            self.chars.clear();
+            self.rollovers.clear();
+        } else {
+            self.chars.truncate(char_len);
+
+            // rollovers at or past char_len belong to chars that were removed
+            let rollover_count = self.rollovers_until(char_len - 1);
+            self.rollovers.truncate(rollover_count);
+        }
+
+        if let Some(astral) = &mut self.astral {
+            let astral_count = astral.partition_point(|&char_idx| char_idx < char_len);
+            astral.truncate(astral_count);
+        }
+
+        self.char_len = char_len;
+    }
+
+    /// Reserves space for the offsets of at least `additional` more chars, unless this index is in the ascii niche and stores no offsets.
+    pub(crate) fn reserve(&mut self, additional: usize) {
+        if !self.is_ascii() {
+            self.chars.reserve(additional);
+        }
+    }
+
+    /// Shrinks the allocations of this index to fit its contents.
+    pub(crate) fn shrink_to_fit(&mut self) {
+        self.chars.shrink_to_fit();
+        self.rollovers.shrink_to_fit();
+
+        if let Some(astral) = &mut self.astral {
+            astral.shrink_to_fit();
+        }
+    }
+
+    /// Appends the offset of the next char to the index, rolling over if it does not fit in O.
+    ///
+    /// `char_idx` must be the index of the char being pushed (the current length of chars),
+    /// and `offset` must be its full offset, which can not be less than the previously pushed offset.
+    fn push_offset(&mut self, char_idx: usize, offset: usize) {
+        debug_assert_eq!(char_idx, self.chars.len());
+
+        let (stored, new_rollovers) = Self::split_offset(offset, self.rollovers.len());
+
+        // chars cannot grow by more than 3 bytes at once so this rolls over at most once for them,
+        // but other units such as grapheme clusters can be long enough to need several rollovers
+        self.rollovers
+            .extend(iter::repeat(char_idx).take(new_rollovers));
+        self.chars.push(O::from_usize(stored));
+    }
+
+    /// Splits a full offset into the value to store for it and the amount of new rollovers it needs,
+    /// given the amount of rollovers that already happened before it.
+    fn split_offset(offset: usize, rollovers: usize) -> (usize, usize) {
+        // the rollovers so far are worth at most the previous offset, which is less than the string length,
+        // so neither the product nor the subtraction can overflow
+        debug_assert!(rollovers * O::MAX <= offset);
+        let offset_idx = offset - rollovers * O::MAX;
+
+        // an offset of exactly O::MAX is still stored as is, see the invariant on rollovers
+        let new_rollovers = offset_idx.saturating_sub(1) / O::MAX;
+
+        (offset_idx - new_rollovers * O::MAX, new_rollovers)
+    }
+
+    /// Adds a stored offset and the rollovers that apply to it back up to the full offset, the inverse of [`split_offset`][Self::split_offset].
+    #[inline]
+    fn join_offset(stored: usize, rollovers: usize) -> usize {
+        stored + O::MAX * rollovers
+    }
+
+    /// Creates an index for the chars in `range` of the string this index was created with,
+    /// returning the byte range of the substring alongside it.
+    ///
+    /// The new index is derived from the offsets already stored rather than by rescanning the string.
+    /// Returns None if the range is reversed or out of bounds.
+    pub(crate) fn slice(&self, buf: &str, range: Range<usize>) -> Option<(Range<usize>, Self)> {
+        if range.start > range.end {
+            return None;
+        }
+
+        let start = self.char_boundary(buf, range.start)?;
+        let end = self.char_boundary(buf, range.end)?;
+
+        // keep the UTF-16 index of the chars in range, counted from the start of the slice
+        let astral = self.astral.as_ref().map(|astral| {
+            let from = astral.partition_point(|&char_idx| char_idx < range.start);
+            let to = astral.partition_point(|&char_idx| char_idx < range.end);
+
+            astral[from..to]
+                .iter()
+                .map(|&char_idx| char_idx - range.start)
+                .collect()
+        });
 
-        Self { chars, rollovers }
+        // if the byte length equals the char length the slice is all ascii and needs no offsets
+        if self.is_ascii() || end - start == range.end - range.start {
+            let ascii = Self {
+                chars: Vec::new(),
+                rollovers: Vec::new(),
+                char_len: range.len(),
+                astral,
+            };
+
+            return Some((start..end, ascii));
+        }
+
+        let base = start - range.start;
+
+        let mut sliced = Self {
+            chars: Vec::with_capacity(range.len()),
+            rollovers: Vec::new(),
+            char_len: range.len(),
+            astral,
+        };
+
+        for (char_idx, offset) in self.offsets(range).enumerate() {
+            sliced.push_offset(char_idx, offset - base);
+        }
+
+        Some((start..end, sliced))
     }
 
     /// Returns whether the string for this index contains only ascii characters.
     /// An empty string is also technically only ascii for the purposes of this function.
+    #[inline]
     pub(crate) fn is_ascii(&self) -> bool {
         self.chars.is_empty()
     }
 
-    /// Computes the amount of chars in the given string in O(1) time,
+    /// Returns the amount of times the offsets overflowed O while building this index.
+    pub(crate) fn rollover_count(&self) -> usize {
+        self.rollovers.len()
+    }
+
+    /// Returns the amount of heap memory in bytes used by this index, not including the string it indexes.
+    pub(crate) fn memory_footprint(&self) -> usize {
+        self.chars.capacity() * mem::size_of::<O>()
+            + self.rollovers.capacity() * mem::size_of::<usize>()
+            + self.astral.as_ref().map_or(0, Vec::capacity) * mem::size_of::<usize>()
+    }
+
+    /// Returns the amount of offsets the index can hold without reallocating.
+    pub(crate) fn capacity_chars(&self) -> usize {
+        self.chars.capacity()
+    }
+
+    /// Returns the amount of rollovers the index can hold without reallocating.
+    pub(crate) fn capacity_rollovers(&self) -> usize {
+        self.rollovers.capacity()
+    }
+
+    /// Returns the amount of chars in the given string in O(1) time,
     /// the string passed must be the one this index was created with.
     pub(crate) fn char_count(&self, buf: &str) -> usize {
-        if self.is_ascii() {
-            buf.len()
-        } else {
-            self.chars.len()
-        }
+        // catch any update path that let the cached length drift from the string
+        debug_assert_eq!(
+            self.char_len,
+            if self.is_ascii() {
+                buf.len()
+            } else {
+                self.chars.len()
+            }
+        );
+
+        self.char_len
     }
 
     /// Gets a char from a string using the index, the string passed must be the one this index was created with
+    #[inline]
     pub(crate) fn get_char(&self, buf: &str, index: usize) -> Option<char> {
+        let byte_idx = self.char_to_byte(buf, index)?;
+
+        // explicitly unwrap here because a None indicates a bug on our end
+        Some(buf[byte_idx..].chars().next().unwrap())
+    }
+
+    /// Gets a char from a string using the index without any bounds checks.
+    ///
+    /// # Safety
+    /// The string passed must be the one this index was created with, and `index` must be less than the char count of that string.
+    #[cfg(feature = "unsafe")]
+    #[allow(unsafe_code)]
+    pub(crate) unsafe fn get_char_unchecked(&self, buf: &str, index: usize) -> char {
+        let byte_idx = if self.is_ascii() {
+            index
+        } else {
+            // SAFETY: caller guarantees index is in bounds of chars, which has one entry per char
+            let offset = self.chars.get_unchecked(index).to_usize();
+
+            index + Self::join_offset(offset, self.rollovers_until(index))
+        };
+
+        // SAFETY: the index is valid for this string, so byte_idx lies on a char boundary before the end of buf
+        match buf.get_unchecked(byte_idx..).chars().next() {
+            Some(c) => c,
+            None => core::hint::unreachable_unchecked(),
+        }
+    }
+
+    /// Converts a char index into the byte index where that char starts in the string,
+    /// the string passed must be the one this index was created with.
+    #[inline]
+    pub(crate) fn char_to_byte(&self, buf: &str, index: usize) -> Option<usize> {
         // niche on empty chars (ascii optimization)
         if self.is_ascii() {
-            // insert this check because .get(index..) will return Some("") on index == buf.len()
-            // but index == buf.len() is a None for us
-            // we can then use the Index trait and assume its looser check is optimized away
-            if index >= buf.len() {
-                return None;
-            }
-
-            // explicitly unwrap in chars because a None indicates a bug on our end.
-            return Some(buf[index..].chars().next().unwrap());
+            // every char is one byte, so the char index is the byte index,
+            // but index == buf.len() is not a char in buf so it is out of bounds
+            return if index < buf.len() { Some(index) } else { None };
         }
 
         // if its in self.chars we can assume its in buf
-        let mut offset = usize::from(*self.chars.get(index)?);
+        Some(index + self.offset(index)?)
+    }
 
-        offset += usize::from(u8::MAX)
-            * self
-                .rollovers
-                .binary_search(&index)
-                // we inc by 1 if variant is Ok as we want to do the rollover of the
-                // index where it "would" be regardless if its found, never its actual location
-                .map_or_else(|e| e, |t| t + 1);
+    /// Returns an iterator over the byte index of every char in the backing string, in order.
+    pub(crate) fn char_offsets(&self) -> impl Iterator<Item = usize> + '_ {
+        // an ascii index stores no offsets, as they would all be 0
+        let stored = if self.is_ascii() {
+            0..0
+        } else {
+            0..self.char_len
+        };
 
-        // explicitly unwrap here because a None indicates a bug on our end
-        Some(buf[index + offset..].chars().next().unwrap())
+        self.offsets(stored)
+            .chain(iter::repeat(0))
+            .zip(0..self.char_len)
+            .map(|(offset, index)| index + offset)
     }
-}
 
-#[cfg(test)]
-extern crate std;
+    /// Counts the occurrences of `c` in the backing string.
+    pub(crate) fn count_matches(&self, buf: &str, c: char) -> usize {
+        match u8::try_from(u32::from(c)) {
+            // ascii bytes never occur inside a multi byte char, so counting bytes is exact
+            Ok(byte) if byte.is_ascii() => count_bytes(buf.as_bytes(), |b| b == byte),
+            // an ascii string cannot contain a non ascii char
+            _ if self.is_ascii() => 0,
+            _ => buf.matches(c).count(),
+        }
+    }
 
-#[test]
-fn create() {
-    use alloc::format;
+    /// Returns `true` if `c` occurs in the backing string.
+    pub(crate) fn contains_char(&self, buf: &str, c: char) -> bool {
+        match u8::try_from(u32::from(c)) {
+            // ascii bytes never occur inside a multi byte char, so searching bytes is exact
+            Ok(byte) if byte.is_ascii() => contains_byte(buf.as_bytes(), byte),
+            // an ascii string cannot contain a non ascii char
+            _ if self.is_ascii() => false,
+            _ => buf.contains(c),
+        }
+    }
 
-    let s_buf = "foo";
-    let s = IndexedCharsInner::new(s_buf);
+    /// Converts a char index into a byte index like [`char_to_byte`][Self::char_to_byte], guided by `rollover`,
+    /// the amount of rollovers that applied to a recently converted index, which is updated for this index.
+    ///
+    /// Nearby indices need at most one rollover step instead of a binary search, which makes sequential access O(1).
+    pub(crate) fn char_to_byte_hinted(
+        &self,
+        buf: &str,
+        index: usize,
+        rollover: &mut usize,
+    ) -> Option<usize> {
+        if self.is_ascii() {
+            return if index < buf.len() { Some(index) } else { None };
+        }
 
-    assert_eq!(s.char_count(&s_buf), 3);
-    assert!(s.rollovers.is_empty());
-    assert!(s.is_ascii());
-    assert_eq!(s.get_char(s_buf, 4), None);
+        let offset = self.chars.get(index)?.to_usize();
 
-    let special = '💯';
+        // whether exactly `count` rollovers apply to index
+        let applies = |count: usize| {
+            (count == 0 || self.rollovers[count - 1] <= index)
+                && (count == self.rollovers.len() || self.rollovers[count] > index)
+        };
 
-    let foo_alloc = format!("{special}a");
+        if !applies(*rollover) {
+            *rollover = if *rollover < self.rollovers.len() && applies(*rollover + 1) {
+                *rollover + 1
+            } else if *rollover > 0 && applies(*rollover - 1) {
+                *rollover - 1
+            } else {
+                // too far from the last index, fall back to searching
+                self.rollovers_until(index)
+            };
+        }
 
-    let foo_s = IndexedCharsInner::new(&foo_alloc);
+        Some(index + Self::join_offset(offset, *rollover))
+    }
 
-    assert_eq!(
-        foo_s.chars,
-        &[0, u8::try_from(special.len_utf8() - 1).unwrap()]
-    );
+    /// Gets the substring spanning the chars in `range`, the string passed must be the one this index was created with.
+    ///
+    /// Returns None if the range is reversed or out of bounds.
+    pub(crate) fn get_str<'s>(&self, buf: &'s str, range: Range<usize>) -> Option<&'s str> {
+        self.char_to_byte_range(buf, range).map(|bytes| &buf[bytes])
+    }
 
-    assert_eq!(foo_s.get_char(&foo_alloc, 2), None);
+    /// Checks that a range of chars lies within the string this index was created with, returning why it does not otherwise.
+    ///
+    /// Every lookup of a range that passes this check succeeds.
+    pub(crate) fn check_char_range(
+        &self,
+        buf: &str,
+        range: &Range<usize>,
+    ) -> Result<(), CharIndexError> {
+        if range.start > range.end {
+            Err(CharIndexError::InvalidRange)
+        } else if range.end > self.char_count(buf) {
+            Err(CharIndexError::OutOfBounds)
+        } else {
+            Ok(())
+        }
+    }
 
-    assert_eq!(foo_s.char_count(&foo_alloc), 2);
-}
+    /// Converts a range of chars into the range of bytes spanning them, the string passed must be the one this index was created with.
+    ///
+    /// Returns None if the range is reversed or out of bounds.
+    pub(crate) fn char_to_byte_range(
+        &self,
+        buf: &str,
+        range: Range<usize>,
+    ) -> Option<Range<usize>> {
+        if range.start > range.end {
+            return None;
+        }
 
-#[cfg(test)]
-fn get_idx(s: &str) {
-    let index = IndexedCharsInner::new(&s);
+        let start = self.char_boundary(buf, range.start)?;
+        let end = self.char_boundary(buf, range.end)?;
 
-    for (char_idx, (_real_idx, c)) in s.char_indices().enumerate() {
-        assert_eq!(index.get_char(&s, char_idx).unwrap(), c);
+        Some(start..end)
     }
 
-    assert_eq!(index.get_char(&s, index.char_count(&s)), None)
-}
+    /// Converts a byte index into the index of the char starting at that byte,
+    /// the string passed must be the one this index was created with.
+    ///
+    /// Returns None if the byte index is out of bounds or does not lie on a char boundary.
+    pub(crate) fn byte_to_char(&self, buf: &str, byte_idx: usize) -> Option<usize> {
+        if byte_idx >= buf.len() || !buf.is_char_boundary(byte_idx) {
+            return None;
+        }
 
-#[test]
-fn uniform() {
-    use alloc::string::String;
-    use rand::{seq::SliceRandom, thread_rng};
+        if self.is_ascii() {
+            return Some(byte_idx);
+        }
 
-    let mut chars: Vec<_> = (0..20_000)
-        .map(|i| char::from_u32(i).unwrap())
-        .cycle()
-        .take(100_000)
-        .collect();
+        // byte indexes of chars are strictly increasing, so we can binary search for the char index.
+        // every char is between 1 and 4 bytes, which bounds where the char index can be
+        let mut low = byte_idx / 4;
+        let mut high = byte_idx.min(self.chars.len() - 1);
 
-    chars.shuffle(&mut thread_rng());
+        while low < high {
+            let mid = low + (high - low) / 2;
 
-    let s = String::from_iter(&chars);
+            // unwrap safe as mid is always below self.chars.len()
+            if mid + self.offset(mid).unwrap() < byte_idx {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
 
-    get_idx(&s);
-    // test ascii optimization
-    get_idx("abcdefghijk");
-}
+        // we checked that byte_idx is a char boundary, so some char must start there
+        debug_assert_eq!(low + self.offset(low).unwrap(), byte_idx);
 
-#[test]
-fn asciiopt() {
-    let ascii = "abcdefghijklmnopqrstuvwxyz";
+        Some(low)
+    }
 
-    let ichars = IndexedCharsInner::new(ascii);
+    /// Converts a byte index into a char index like [`byte_to_char`][Self::byte_to_char],
+    /// but also accepts the length of the string, returning the char count.
+    pub(crate) fn byte_to_char_boundary(&self, buf: &str, byte_idx: usize) -> Option<usize> {
+        if byte_idx == buf.len() {
+            Some(self.char_count(buf))
+        } else {
+            self.byte_to_char(buf, byte_idx)
+        }
+    }
 
-    assert!(ichars.is_ascii());
+    /// Counts the chars in the substring spanning the bytes in `range` with two binary searches.
+    ///
+    /// Returns None if the range is reversed, out of bounds, or either end does not lie on a char boundary.
+    pub(crate) fn char_count_in_range(&self, buf: &str, range: Range<usize>) -> Option<usize> {
+        if range.start > range.end {
+            return None;
+        }
 
-    assert!(ichars.rollovers.is_empty());
-    assert!(ichars.chars.is_empty());
+        let start = self.byte_to_char_boundary(buf, range.start)?;
+        let end = self.byte_to_char_boundary(buf, range.end)?;
+
+        Some(end - start)
+    }
+
+    /// Converts a char index into the index of the first UTF-16 code unit of that char, accepting the index one past the last char.
+    /// The string passed must be the one this index was created with.
+    ///
+    /// Chars of 4 bytes in UTF-8 are exactly those that take 2 code units, with a UTF-16 index they are counted with a binary search,
+    /// otherwise this counts their leading bytes before the char, which is O(n).
+    pub(crate) fn char_to_utf16(&self, buf: &str, index: usize) -> Option<usize> {
+        let byte_idx = self.char_boundary(buf, index)?;
+
+        if self.is_ascii() {
+            return Some(index);
+        }
+
+        let surrogate_pairs = match &self.astral {
+            Some(astral) => astral.partition_point(|&char_idx| char_idx < index),
+            None => buf.as_bytes()[..byte_idx]
+                .iter()
+                .filter(|&&b| b >= 0xF0)
+                .count(),
+        };
+
+        Some(index + surrogate_pairs)
+    }
+
+    /// Converts a UTF-16 code unit index into a char index, accepting the index one past the last code unit.
+    /// The string passed must be the one this index was created with.
+    ///
+    /// Returns None if the index is out of bounds or points between the two code units of a surrogate pair,
+    /// this is O(log n) with a UTF-16 index and O(n) without one.
+    pub(crate) fn utf16_to_char(&self, buf: &str, utf16_idx: usize) -> Option<usize> {
+        if self.is_ascii() {
+            return if utf16_idx <= buf.len() {
+                Some(utf16_idx)
+            } else {
+                None
+            };
+        }
+
+        if let Some(astral) = &self.astral {
+            return self.utf16_to_char_indexed(astral, utf16_idx);
+        }
+
+        let mut units = 0;
+
+        for (char_idx, c) in buf.chars().enumerate() {
+            if units >= utf16_idx {
+                return if units == utf16_idx {
+                    Some(char_idx)
+                } else {
+                    None
+                };
+            }
+
+            units += c.len_utf16();
+        }
+
+        if units == utf16_idx {
+            Some(self.char_count(buf))
+        } else {
+            None
+        }
+    }
 
-    assert_eq!(ichars.get_char(&ascii, ascii.len()), None);
-    assert_eq!(ichars.get_char(&ascii, ascii.len() - 1), Some('z'));
+    /// Converts a UTF-16 code unit index into a char index like [`utf16_to_char`][Self::utf16_to_char], using the UTF-16 index `astral`.
+    fn utf16_to_char_indexed(&self, astral: &[usize], utf16_idx: usize) -> Option<usize> {
+        // the nth astral char starts at its char index plus the n surrogate pairs before it, which is sorted as well,
+        // partition_point can not see the position of an element so this searches for the amount starting before utf16_idx by hand
+        let (mut low, mut high) = (0, astral.len());
+
+        while low < high {
+            let mid = low + (high - low) / 2;
+
+            if astral[mid] + mid < utf16_idx {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+
+        // the last astral char before the index must end before it, or the index points at its second code unit
+        if low > 0 && astral[low - 1] + low == utf16_idx {
+            return None;
+        }
+
+        // every astral char before the index took one more unit than its char
+        let char_idx = utf16_idx - low;
+
+        if char_idx <= self.char_len {
+            Some(char_idx)
+        } else {
+            None
+        }
+    }
+
+    /// Converts a char index into its zero based line and column, where lines are separated by `\n` and columns are counted in chars.
+    /// The index one past the last char is accepted, and None is returned if the index is out of bounds.
+    ///
+    /// Lines before the char are counted by scanning bytes, so this is O(n) in the byte index of the char.
+    pub(crate) fn line_col(&self, buf: &str, index: usize) -> Option<(usize, usize)> {
+        let byte_idx = self.char_boundary(buf, index)?;
+        let before = &buf[..byte_idx];
+
+        let line = count_bytes(before.as_bytes(), |b| b == b'\n');
+        let line_start = before.rfind('\n').map_or(0, |newline| newline + 1);
+
+        // the line start is right after a newline, so it is always a char boundary
+        let column = index - self.byte_to_char_boundary(buf, line_start)?;
+
+        Some((line, column))
+    }
+
+    /// Converts a display column into the index of the char covering it, the column one past the last char is accepted and returns the char count.
+    /// Returns None if the column is past the end of the string.
+    #[cfg(feature = "unicode-width")]
+    pub(crate) fn width_to_char(&self, buf: &str, column: usize) -> Option<usize> {
+        let mut start = 0;
+
+        for (char_idx, c) in buf.chars().enumerate() {
+            let end = start + char_width(c);
+
+            // zero width chars cover no columns, so they are never returned
+            if column < end {
+                return Some(char_idx);
+            }
+
+            start = end;
+        }
+
+        if column == start {
+            Some(self.char_count(buf))
+        } else {
+            None
+        }
+    }
+
+    /// Converts a char index into a byte index like [`char_to_byte`][Self::char_to_byte],
+    /// but also accepts the index one past the last char, returning the length of the string.
+    pub(crate) fn char_boundary(&self, buf: &str, index: usize) -> Option<usize> {
+        if index == self.char_count(buf) {
+            Some(buf.len())
+        } else {
+            self.char_to_byte(buf, index)
+        }
+    }
+
+    /// Computes the offset from a char index to its byte index, returns None if the index is out of bounds.
+    ///
+    /// Must not be called on an ascii index, as those store no offsets.
+    #[inline]
+    fn offset(&self, index: usize) -> Option<usize> {
+        let offset = self.chars.get(index)?.to_usize();
+
+        Some(Self::join_offset(offset, self.rollovers_until(index)))
+    }
+
+    /// Returns the amount of rollovers that apply to the given char index.
+    #[inline]
+    fn rollovers_until(&self, index: usize) -> usize {
+        // rollovers at the index itself apply to it, and there may be several of them
+        // when indexing units longer than O::MAX bytes, so binary_search would not be enough
+        // a hand written branchless search was measured to be slower than partition_point, see the rollovers bench
+        self.rollovers
+            .partition_point(|&rollover| rollover <= index)
+    }
+
+    /// Returns an iterator over the full offsets of every char in the given range.
+    ///
+    /// This only performs one binary search for the start of the range,
+    /// so it is cheaper than calling [`offset`][Self::offset] for each char.
+    /// Must not be called on an ascii index, and the range must be in bounds.
+    fn offsets(&self, range: Range<usize>) -> impl Iterator<Item = usize> + '_ {
+        let mut rollover = self.rollovers_until(range.start);
+
+        self.chars[range.clone()]
+            .iter()
+            .zip(range)
+            .map(move |(&offset, char_idx)| {
+                // rollovers are sorted, so we can step through them as we go
+                while self.rollovers.get(rollover) == Some(&char_idx) {
+                    rollover += 1;
+                }
+
+                Self::join_offset(offset.to_usize(), rollover)
+            })
+    }
+}
+
+impl<O> IndexedCharsInner<O> {
+    /// Returns `true` if both indexed strings have the same amount of chars, which equal strings must.
+    ///
+    /// This is O(1) with the cached char counts, so comparisons between indexed strings can bail out before comparing any bytes.
+    pub(crate) fn same_char_count<P>(&self, other: &IndexedCharsInner<P>) -> bool {
+        self.char_len == other.char_len
+    }
+
+    /// Creates the index of an ascii string of `len` bytes, which is the ascii niche and allocates nothing.
+    ///
+    /// The string must be ascii, this is not checked.
+    pub(crate) const fn new_ascii(len: usize) -> Self {
+        Self {
+            chars: Vec::new(),
+            rollovers: Vec::new(),
+            char_len: len,
+            astral: None,
+        }
+    }
+}
+
+/// Returns the width of a string when displayed in a terminal, in columns.
+#[cfg(feature = "unicode-width")]
+pub(crate) fn display_width(s: &str) -> usize {
+    s.chars().map(char_width).sum()
+}
+
+/// Returns the display width of a char in columns, control chars have no width.
+#[cfg(feature = "unicode-width")]
+fn char_width(c: char) -> usize {
+    unicode_width::UnicodeWidthChar::width(c).unwrap_or(0)
+}
+
+/// Returns the NFC form of a string, borrowing it if it is already normalized.
+#[cfg(feature = "unicode-normalization")]
+pub(crate) fn normalize_nfc(s: &str) -> alloc::borrow::Cow<'_, str> {
+    use alloc::borrow::Cow;
+    use unicode_normalization::{is_nfc_quick, IsNormalized, UnicodeNormalization};
+
+    // the quick check can answer Maybe, in which case it is cheaper to just normalize
+    if is_nfc_quick(s.chars()) == IsNormalized::Yes {
+        Cow::Borrowed(s)
+    } else {
+        Cow::Owned(s.nfc().collect())
+    }
+}
+
+/// Returns the char count to build the index of `s` with from a hint of it, which may be wrong in release builds.
+///
+/// Checking for ascii is much cheaper than counting chars, so the niche is decided from the string rather than the hint,
+/// this keeps a wrong count from putting a non ascii string in the niche, or an ascii string out of it.
+/// Any other wrong count is corrected while indexing.
+fn niche_char_count(s: &str, hint: usize) -> usize {
+    if s.is_ascii() {
+        s.len()
+    } else if hint == s.len() {
+        count_chars(s)
+    } else {
+        hint
+    }
+}
+
+/// Counts the chars in a string.
+#[cfg(not(feature = "simd"))]
+fn count_chars(s: &str) -> usize {
+    s.chars().count()
+}
+
+/// Counts the chars in a string by counting the bytes that are not utf8 continuation bytes (`0b10xx_xxxx`).
+///
+/// This is the fallback for targets without SSE2 or NEON, or without the `unsafe` feature, which relies on [`count_bytes`] being vectorized.
+#[cfg(all(
+    feature = "simd",
+    not(all(
+        feature = "unsafe",
+        any(
+            all(
+                any(target_arch = "x86", target_arch = "x86_64"),
+                target_feature = "sse2"
+            ),
+            all(target_arch = "aarch64", target_feature = "neon")
+        )
+    ))
+))]
+fn count_chars(s: &str) -> usize {
+    count_bytes(s.as_bytes(), |b| b & 0xC0 != 0x80)
+}
+
+/// Amount of 64 byte chunks whose continuation bytes can be counted in 8 bit lanes before a lane could overflow,
+/// each chunk adds at most 4 to a lane.
+#[cfg(all(
+    feature = "simd",
+    feature = "unsafe",
+    any(
+        all(
+            any(target_arch = "x86", target_arch = "x86_64"),
+            target_feature = "sse2"
+        ),
+        all(target_arch = "aarch64", target_feature = "neon")
+    )
+))]
+const CHUNKS_PER_SUM: usize = u8::MAX as usize / 4;
+
+/// Counts the chars in a string with SSE2, by subtracting the amount of utf8 continuation bytes from its length.
+///
+/// Continuation bytes (`0x80..=0xBF`) are exactly the bytes below -64 when read as `i8`, so each 16 byte block takes one compare,
+/// and the matches are counted in 8 bit lanes that are only summed every [`CHUNKS_PER_SUM`] chunks of 4 blocks.
+#[cfg(all(
+    feature = "simd",
+    feature = "unsafe",
+    any(target_arch = "x86", target_arch = "x86_64"),
+    target_feature = "sse2"
+))]
+#[allow(unsafe_code)]
+fn count_chars(s: &str) -> usize {
+    #[cfg(target_arch = "x86")]
+    use core::arch::x86::{
+        __m128i, _mm_add_epi64, _mm_add_epi8, _mm_cmplt_epi8, _mm_cvtsi128_si32, _mm_loadu_si128,
+        _mm_sad_epu8, _mm_set1_epi8, _mm_setzero_si128, _mm_sub_epi8, _mm_unpackhi_epi64,
+    };
+    #[cfg(target_arch = "x86_64")]
+    use core::arch::x86_64::{
+        __m128i, _mm_add_epi64, _mm_add_epi8, _mm_cmplt_epi8, _mm_cvtsi128_si32, _mm_loadu_si128,
+        _mm_sad_epu8, _mm_set1_epi8, _mm_setzero_si128, _mm_sub_epi8, _mm_unpackhi_epi64,
+    };
+
+    let bytes = s.as_bytes();
+    let mut chunks = bytes.chunks_exact(64);
+    let mut continuations = 0;
+
+    // SAFETY: sse2 is enabled for this target, and the loads read the 4 blocks of 16 bytes of one chunk, which need not be aligned
+    unsafe {
+        let threshold = _mm_set1_epi8(-64);
+        // matching lanes are all ones, which is -1
+        let matches = |block: *const __m128i| _mm_cmplt_epi8(_mm_loadu_si128(block), threshold);
+
+        while chunks.len() != 0 {
+            let mut lanes = _mm_setzero_si128();
+
+            for chunk in chunks.by_ref().take(CHUNKS_PER_SUM) {
+                // loadu does not need the pointer to be aligned
+                #[allow(clippy::cast_ptr_alignment)]
+                let block = chunk.as_ptr().cast::<__m128i>();
+
+                let low = _mm_add_epi8(matches(block), matches(block.add(1)));
+                let high = _mm_add_epi8(matches(block.add(2)), matches(block.add(3)));
+
+                lanes = _mm_sub_epi8(lanes, _mm_add_epi8(low, high));
+            }
+
+            // sums each half of the lanes into a 64 bit lane, then adds the halves
+            let sums = _mm_sad_epu8(lanes, _mm_setzero_si128());
+            let sum = _mm_add_epi64(sums, _mm_unpackhi_epi64(sums, sums));
+
+            // unwrap safe as the sum is at most 16 * 255
+            continuations += usize::try_from(_mm_cvtsi128_si32(sum)).unwrap();
+        }
+    }
+
+    let remainder = chunks.remainder();
+
+    bytes.len() - continuations - remainder.iter().filter(|&&b| b & 0xC0 == 0x80).count()
+}
+
+/// Counts the chars in a string with NEON, by subtracting the amount of utf8 continuation bytes from its length.
+///
+/// This works like the SSE2 version, see there for details.
+/// NEON intrinsics were only stabilized in Rust 1.59, which this combination of features and target needs over the crate MSRV.
+#[cfg(all(
+    feature = "simd",
+    feature = "unsafe",
+    target_arch = "aarch64",
+    target_feature = "neon"
+))]
+#[allow(unsafe_code, clippy::incompatible_msrv)]
+fn count_chars(s: &str) -> usize {
+    use core::arch::aarch64::{
+        uint8x16_t, vaddlvq_u8, vaddq_u8, vcltq_s8, vdupq_n_s8, vdupq_n_u8, vld1q_s8, vsubq_u8,
+    };
+
+    let bytes = s.as_bytes();
+    let mut chunks = bytes.chunks_exact(64);
+    let mut continuations = 0;
+
+    // SAFETY: neon is enabled for this target, and the loads read the 4 blocks of 16 bytes of one chunk
+    unsafe {
+        let threshold = vdupq_n_s8(-64);
+        // matching lanes are all ones, which wraps to subtracting -1
+        let matches = |block: *const i8| -> uint8x16_t { vcltq_s8(vld1q_s8(block), threshold) };
+
+        while chunks.len() != 0 {
+            let mut lanes = vdupq_n_u8(0);
+
+            for chunk in chunks.by_ref().take(CHUNKS_PER_SUM) {
+                let block = chunk.as_ptr().cast::<i8>();
+
+                let low = vaddq_u8(matches(block), matches(block.add(16)));
+                let high = vaddq_u8(matches(block.add(32)), matches(block.add(48)));
+
+                lanes = vsubq_u8(lanes, vaddq_u8(low, high));
+            }
+
+            continuations += usize::from(vaddlvq_u8(lanes));
+        }
+    }
+
+    let remainder = chunks.remainder();
+
+    bytes.len() - continuations - remainder.iter().filter(|&&b| b & 0xC0 == 0x80).count()
+}
+
+/// Counts the bytes matching a predicate.
+#[cfg(not(feature = "simd"))]
+fn count_bytes(bytes: &[u8], f: impl Fn(u8) -> bool) -> usize {
+    bytes.iter().filter(|&&b| f(b)).count()
+}
+
+/// Counts the bytes matching a predicate.
+///
+/// The bytes are processed in fixed size chunks with a narrow counter, which the compiler vectorizes,
+/// so this is much faster on large strings without needing `unsafe` intrinsics.
+#[cfg(feature = "simd")]
+fn count_bytes(bytes: &[u8], f: impl Fn(u8) -> bool) -> usize {
+    /// Amount of bytes counted at once, small enough that the count for a chunk fits in a u8
+    const CHUNK_LEN: usize = 128;
+
+    let mut chunks = bytes.chunks_exact(CHUNK_LEN);
+    let mut count = 0;
+
+    for chunk in &mut chunks {
+        count += usize::from(chunk.iter().fold(0, |n: u8, &b| n + u8::from(f(b))));
+    }
+
+    count + chunks.remainder().iter().filter(|&&b| f(b)).count()
+}
+
+/// Returns `true` if `byte` occurs in `bytes`.
+#[cfg(not(feature = "simd"))]
+fn contains_byte(bytes: &[u8], byte: u8) -> bool {
+    bytes.contains(&byte)
+}
+
+/// Returns `true` if `byte` occurs in `bytes`.
+///
+/// Each chunk is searched without branching on every byte, which the compiler vectorizes,
+/// stopping at the first chunk that contains a match.
+#[cfg(feature = "simd")]
+fn contains_byte(bytes: &[u8], byte: u8) -> bool {
+    /// Amount of bytes searched at once
+    const CHUNK_LEN: usize = 128;
+
+    let mut chunks = bytes.chunks_exact(CHUNK_LEN);
+
+    chunks.any(|chunk| {
+        chunk
+            .iter()
+            .fold(0, |found, &b| found | u8::from(b == byte))
+            != 0
+    }) || chunks.remainder().contains(&byte)
+}
+
+#[cfg(test)]
+extern crate std;
+
+#[test]
+fn create() {
+    use alloc::format;
+
+    let s_buf = "foo";
+    let s = IndexedCharsInner::<u8>::new(s_buf);
+
+    assert_eq!(s.char_count(s_buf), 3);
+    assert!(s.rollovers.is_empty());
+    assert!(s.is_ascii());
+    assert_eq!(s.get_char(s_buf, 4), None);
+
+    let special = '💯';
+
+    let foo_alloc = format!("{special}a");
+
+    let foo_s = IndexedCharsInner::<u8>::new(&foo_alloc);
+
+    assert_eq!(
+        foo_s.chars,
+        &[0, u8::try_from(special.len_utf8() - 1).unwrap()]
+    );
+
+    assert_eq!(foo_s.get_char(&foo_alloc, 2), None);
+
+    assert_eq!(foo_s.char_count(&foo_alloc), 2);
+}
+
+#[cfg(test)]
+fn get_idx<O: OffsetInt>(s: &str) {
+    let index = IndexedCharsInner::<O>::new(s);
+
+    for (char_idx, (real_idx, c)) in s.char_indices().enumerate() {
+        assert_eq!(index.get_char(s, char_idx).unwrap(), c);
+        assert_eq!(index.char_to_byte(s, char_idx), Some(real_idx));
+        assert_eq!(index.byte_to_char(s, real_idx), Some(char_idx));
+        #[cfg(feature = "unsafe")]
+        #[allow(unsafe_code)]
+        {
+            // SAFETY: char_idx comes from iterating s, so it is in bounds
+            assert_eq!(unsafe { index.get_char_unchecked(s, char_idx) }, c);
+        }
+
+        if c.len_utf8() > 1 {
+            assert_eq!(index.byte_to_char(s, real_idx + 1), None);
+        }
+    }
+
+    assert_eq!(index.get_char(s, index.char_count(s)), None);
+    assert_eq!(index.char_to_byte(s, index.char_count(s)), None);
+    assert_eq!(index.byte_to_char(s, s.len()), None);
+}
+
+#[test]
+fn uniform() {
+    use alloc::string::String;
+    use rand::{seq::SliceRandom, thread_rng};
+
+    let mut chars: Vec<_> = (0..20_000)
+        .map(|i| char::from_u32(i).unwrap())
+        .cycle()
+        .take(100_000)
+        .collect();
+
+    chars.shuffle(&mut thread_rng());
+
+    let s = String::from_iter(&chars);
+
+    get_idx::<u8>(&s);
+    get_idx::<u16>(&s);
+    get_idx::<u32>(&s);
+    // test ascii optimization
+    get_idx::<u8>("abcdefghijk");
+}
+
+#[test]
+fn asciiopt() {
+    let ascii = "abcdefghijklmnopqrstuvwxyz";
+
+    let ichars = IndexedCharsInner::<u8>::new(ascii);
+
+    assert!(ichars.is_ascii());
+
+    assert!(ichars.rollovers.is_empty());
+    assert!(ichars.chars.is_empty());
+    assert_eq!(ichars.memory_footprint(), 0);
+
+    assert_eq!(ichars.get_char(ascii, ascii.len()), None);
+    assert_eq!(ichars.get_char(ascii, ascii.len() - 1), Some('z'));
+}
+
+#[cfg(test)]
+fn check_slice<O: OffsetInt>(s: &str, range: Range<usize>) {
+    let index = IndexedCharsInner::<O>::new(s);
+
+    let (bytes, sliced) = index.slice(s, range.clone()).unwrap();
+
+    let boundaries: Vec<_> = s.char_indices().map(|(i, _)| i).chain([s.len()]).collect();
+
+    assert_eq!(bytes, boundaries[range.start]..boundaries[range.end]);
+    assert_eq!(index.get_str(s, range), Some(&s[bytes.clone()]));
+
+    let fresh = IndexedCharsInner::new(&s[bytes]);
+
+    assert_eq!(sliced.chars, fresh.chars);
+    assert_eq!(sliced.rollovers, fresh.rollovers);
+    assert_eq!(sliced.char_len, fresh.char_len);
+}
+
+#[test]
+fn slice() {
+    use alloc::string::String;
+
+    let s: String = (0..2000)
+        .map(|i| char::from_u32(i * 37 % 3000).unwrap())
+        .chain("plain ascii tail".chars())
+        .collect();
+    let chars = s.chars().count();
+
+    check_slice::<u8>(&s, 0..chars);
+    check_slice::<u8>(&s, 0..0);
+    check_slice::<u8>(&s, chars..chars);
+    check_slice::<u8>(&s, 100..1500);
+    check_slice::<u8>(&s, 2000..chars);
+    check_slice::<u8>("foobar", 1..4);
+    check_slice::<u16>(&s, 100..1500);
+
+    let index = IndexedCharsInner::<u8>::new(&s);
+
+    assert!(index.slice(&s, 0..chars + 1).is_none());
+    assert!(index.slice(&s, 5..chars + 1).is_none());
+    assert!(index.slice(&s, Range { start: 5, end: 4 }).is_none());
+    assert!(index.get_str(&s, 0..chars + 1).is_none());
+    assert!(index.get_str(&s, Range { start: 5, end: 4 }).is_none());
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn parallel() {
+    use alloc::string::String;
+
+    let s: String = (0..100_000)
+        .map(|i| char::from_u32(i * 7919 % 0xD000).unwrap())
+        .collect();
+
+    let serial = IndexedCharsInner::<u8>::new(&s);
+    let serial_wide = IndexedCharsInner::<u16>::new(&s);
+
+    for chunk_len in [1, 3, 1000, 4096, s.len()] {
+        let parallel = IndexedCharsInner::<u8>::new_chunked(&s, chunk_len);
+
+        assert_eq!(parallel.chars, serial.chars);
+        assert_eq!(parallel.rollovers, serial.rollovers);
+        assert_eq!(parallel.char_len, serial.char_len);
+
+        let parallel_wide = IndexedCharsInner::<u16>::new_chunked(&s, chunk_len);
+
+        assert_eq!(parallel_wide.chars, serial_wide.chars);
+        assert_eq!(parallel_wide.rollovers, serial_wide.rollovers);
+    }
+
+    assert!(IndexedCharsInner::<u8>::new_parallel("abc").is_ascii());
+}
+
+#[test]
+fn reuse() {
+    let first = "föobär".repeat(200);
+    let second = "bär".repeat(300);
+
+    let (chars, rollovers) = IndexedCharsInner::<u8>::new(&first).into_vecs();
+    let chars_ptr = chars.as_ptr();
+
+    let reused = IndexedCharsInner::new_in(&second, chars, rollovers);
+    let fresh = IndexedCharsInner::new(&second);
+
+    assert_eq!(reused.chars, fresh.chars);
+    assert_eq!(reused.rollovers, fresh.rollovers);
+    assert_eq!(reused.char_len, fresh.char_len);
+    // the second string has fewer chars, so it fits in the old allocation
+    assert_eq!(reused.chars.as_ptr(), chars_ptr);
+
+    let (chars, rollovers) = reused.into_vecs();
+    let ascii = IndexedCharsInner::new_in("foo", chars, rollovers);
+
+    assert!(ascii.is_ascii());
+    assert_eq!(ascii.get_char("foo", 2), Some('o'));
+}
+
+#[test]
+fn wide_offsets() {
+    let s = "ö".repeat(100_000);
+
+    let narrow = IndexedCharsInner::<u8>::new(&s);
+    let wide = IndexedCharsInner::<u16>::new(&s);
+
+    // each 'ö' adds 1 to the offset, so rollovers happen every O::MAX chars
+    assert_eq!(narrow.rollover_count(), 100_000 / 255);
+    assert_eq!(wide.rollover_count(), 1);
+    assert_eq!(wide.get_char(&s, 99_999), Some('ö'));
+    assert!(wide.memory_footprint() > narrow.memory_footprint());
+}
+
+#[cfg(test)]
+fn check_push<O: OffsetInt>(s: &str) {
+    use alloc::string::String;
+
+    let fresh = IndexedCharsInner::<O>::new(s);
+
+    let mut buf = String::new();
+    let mut pushed = IndexedCharsInner::<O>::new(&buf);
+
+    for c in s.chars() {
+        pushed.push_char(&buf, c);
+        buf.push(c);
+    }
+
+    assert_eq!(pushed.chars, fresh.chars);
+    assert_eq!(pushed.rollovers, fresh.rollovers);
+    assert_eq!(pushed.char_len, fresh.char_len);
+
+    // append in uneven pieces, each piece resumes from where the last one ended
+    for piece_len in [1, 2, 7, 100] {
+        let mut buf = String::new();
+        let mut pushed = IndexedCharsInner::<O>::new(&buf);
+        let mut rest = s;
+
+        while !rest.is_empty() {
+            let mut mid = piece_len.min(rest.len());
+
+            while !rest.is_char_boundary(mid) {
+                mid += 1;
+            }
+
+            let (piece, tail) = rest.split_at(mid);
+
+            pushed.push_str(&buf, piece);
+            buf.push_str(piece);
+            rest = tail;
+        }
+
+        assert_eq!(pushed.chars, fresh.chars);
+        assert_eq!(pushed.rollovers, fresh.rollovers);
+        assert_eq!(pushed.char_len, fresh.char_len);
+    }
+}
+
+#[test]
+fn push() {
+    use alloc::string::String;
+
+    let s: String = (0..2000)
+        .map(|i| char::from_u32(i * 37 % 3000).unwrap())
+        .collect();
+
+    check_push::<u8>("");
+    check_push::<u8>("foobar");
+    // leaves the ascii niche after a prefix of ascii chars
+    check_push::<u8>("foobär");
+    check_push::<u8>(&alloc::format!("{}ä{}", "foo".repeat(50), "bar".repeat(50)));
+    check_push::<u8>(&s);
+    check_push::<u16>(&s);
+    // each 'ö' adds 1 to the offset, so this ends exactly on a rollover
+    check_push::<u8>(&"ö".repeat(256));
+}
+
+#[test]
+fn push_rollover() {
+    use alloc::string::String;
+
+    let mut buf = String::new();
+    let mut index = IndexedCharsInner::<u8>::new(&buf);
+
+    // each 'ö' adds 1 to the offset, the 256th one has an offset of exactly u8::MAX
+    for _ in 0..256 {
+        index.push_char(&buf, 'ö');
+        buf.push('ö');
+    }
+
+    assert_eq!(index.rollover_count(), 0);
+
+    index.push_char(&buf, 'ö');
+    buf.push('ö');
+
+    assert_eq!(index.rollover_count(), 1);
+    assert_eq!(index.rollovers, [256]);
+
+    // a 4 byte char straddling the next rollover
+    for _ in 0..253 {
+        index.push_char(&buf, 'ö');
+        buf.push('ö');
+    }
+
+    index.push_char(&buf, '💯');
+    buf.push('💯');
+    index.push_char(&buf, 'a');
+    buf.push('a');
+
+    assert_eq!(index.rollover_count(), 2);
+    assert_eq!(index.get_char(&buf, 510), Some('💯'));
+    assert_eq!(index.get_char(&buf, 511), Some('a'));
+    assert_eq!(index.char_count(&buf), 512);
+}
+
+#[cfg(test)]
+fn check_truncate<O: OffsetInt>(s: &str, char_len: usize) {
+    let mut index = IndexedCharsInner::<O>::new(s);
+    let byte_len = index.truncate(s, char_len).unwrap();
+
+    let prefix: alloc::string::String = s.chars().take(char_len).collect();
+    let fresh = IndexedCharsInner::<O>::new(&prefix);
+
+    assert_eq!(byte_len, prefix.len());
+    assert_eq!(index.chars, fresh.chars);
+    assert_eq!(index.rollovers, fresh.rollovers);
+    assert_eq!(index.char_len, fresh.char_len);
+}
+
+#[test]
+fn truncate() {
+    let s = alloc::format!("foo{}bar", "ö".repeat(600));
+
+    for char_len in [0, 2, 3, 4, 258, 259, 260, 600, 602, 604] {
+        check_truncate::<u8>(&s, char_len);
+    }
+
+    check_truncate::<u16>(&s, 300);
+    check_truncate::<u8>("foobar", 3);
+
+    let mut index = IndexedCharsInner::<u8>::new(&s);
+
+    assert_eq!(index.truncate(&s, 606), None);
+    assert_eq!(index.truncate(&s, 1000), None);
+    assert_eq!(index.rollover_count(), 2);
+}
+
+#[test]
+fn validate() {
+    let s = "föobär".repeat(200);
+
+    let index = IndexedCharsInner::<u8>::new(&s);
+
+    assert!(index.validate(&s));
+    assert!(!index.validate("föobär"));
+    assert!(IndexedCharsInner::<u8>::new("foo").validate("bar"));
+    assert!(!IndexedCharsInner::<u8>::new("foo").validate("bär"));
+
+    let mut corrupted = index.clone();
+    corrupted.rollovers.pop();
+
+    assert!(!corrupted.validate(&s));
+}
+
+#[cfg(test)]
+fn hash_of<T: core::hash::Hash + ?Sized>(value: &T) -> u64 {
+    use core::hash::Hasher;
+    use std::collections::hash_map::DefaultHasher;
+
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[test]
+fn hash_matches_str() {
+    use crate::{IndexedChars, OwnedIndexedChars, SharedIndexedChars};
+    use alloc::{rc::Rc, string::String};
+    use rand::{thread_rng, Rng};
+    use std::collections::HashMap;
+
+    let mut rng = thread_rng();
+
+    // Borrow<str> requires that we hash exactly like str does
+    for len in 0..200 {
+        let s: String = (0..len)
+            .map(|_| {
+                if rng.gen_bool(0.5) {
+                    char::from(rng.gen_range(b' '..=b'~'))
+                } else {
+                    rng.gen::<char>()
+                }
+            })
+            .collect();
+
+        assert_eq!(hash_of(&IndexedChars::new(&s)), hash_of(s.as_str()));
+        assert_eq!(
+            hash_of(&OwnedIndexedChars::new(s.clone())),
+            hash_of(s.as_str())
+        );
+        assert_eq!(
+            hash_of(&SharedIndexedChars::new(Rc::<str>::from(s.as_str()))),
+            hash_of(s.as_str())
+        );
+    }
+
+    let mut map = HashMap::new();
+    map.insert(OwnedIndexedChars::new(String::from("föo")), 1);
+    map.insert(OwnedIndexedChars::new(String::from("bär")), 2);
+
+    assert_eq!(map.get("föo"), Some(&1));
+    assert_eq!(map.get("bär"), Some(&2));
+    assert_eq!(map.get("foo"), None);
+}
+
+#[test]
+fn count() {
+    use alloc::string::String;
+    use rand::{thread_rng, Rng};
+
+    let mut rng = thread_rng();
+
+    for len in [0, 1, 127, 128, 129, 1000, 10_000] {
+        let s: String = (0..len)
+            .map(|_| {
+                if rng.gen_bool(0.5) {
+                    char::from(rng.gen_range(0..0x80))
+                } else {
+                    rng.gen::<char>()
+                }
+            })
+            .collect();
+
+        assert_eq!(count_chars(&s), len);
+    }
+
+    assert_eq!(count_chars(&"a".repeat(1000)), 1000);
+    // three continuation bytes in every char, enough blocks that the counts per lane reach their limit
+    assert_eq!(count_chars(&"💯".repeat(5000)), 5000);
+}
+
+#[cfg(feature = "unicode-segmentation")]
+#[test]
+fn graphemes() {
+    use alloc::{format, string::String, vec::Vec};
+    use unicode_segmentation::UnicodeSegmentation;
+
+    // combining marks, a zwj sequence, crlf, and a cluster long enough to need several rollovers
+    let long: String = core::iter::once('a')
+        .chain(core::iter::repeat('\u{301}').take(300))
+        .collect();
+    let inputs = [
+        String::new(),
+        String::from("abc"),
+        String::from("ab\r\n"),
+        String::from("e\u{301}x👩‍👩‍👧y"),
+        format!("{long}b{long}"),
+        "ö\u{308}".repeat(500),
+    ];
+
+    for s in &inputs {
+        let graphemes: Vec<(usize, &str)> = s.grapheme_indices(true).collect();
+
+        let index = IndexedCharsInner::<u8>::new_graphemes(s);
+
+        assert_eq!(index.char_count(s), graphemes.len());
+
+        for (i, &(byte_idx, g)) in graphemes.iter().enumerate() {
+            assert_eq!(index.char_to_byte(s, i), Some(byte_idx));
+            assert_eq!(index.get_str(s, i..i + 1), Some(g));
+        }
+
+        assert_eq!(index.char_to_byte(s, graphemes.len()), None);
+    }
+}
+
+#[test]
+fn utf16() {
+    use alloc::string::String;
+
+    let s: String = ['a', 'ö', '€', '💯', 'b', '💯']
+        .iter()
+        .cycle()
+        .take(600)
+        .collect();
+
+    for (s, utf16) in [s.as_str(), "💯💯ö", "foo", ""]
+        .iter()
+        .flat_map(|&s| [(s, false), (s, true)])
+    {
+        let mut index = IndexedCharsInner::<u8>::new(s);
+        if utf16 {
+            index.index_utf16(s);
+        }
+
+        let mut units = 0;
+
+        for (char_idx, c) in s.chars().enumerate() {
+            assert_eq!(index.char_to_utf16(s, char_idx), Some(units));
+            assert_eq!(index.utf16_to_char(s, units), Some(char_idx));
+
+            if c.len_utf16() == 2 {
+                assert_eq!(index.utf16_to_char(s, units + 1), None);
+            }
+
+            units += c.len_utf16();
+        }
+
+        let char_count = index.char_count(s);
+
+        assert_eq!(units, s.encode_utf16().count());
+        assert_eq!(index.char_to_utf16(s, char_count), Some(units));
+        assert_eq!(index.utf16_to_char(s, units), Some(char_count));
+        assert_eq!(index.char_to_utf16(s, char_count + 1), None);
+        assert_eq!(index.utf16_to_char(s, units + 1), None);
+    }
+}
+
+#[test]
+fn utf16_index_updates() {
+    use crate::OwnedIndexedChars;
+    use alloc::string::String;
+
+    fn check(index: &OwnedIndexedChars) {
+        assert!(index.validate());
+
+        let mut units = 0;
+
+        for (char_idx, c) in index.chars().enumerate() {
+            assert_eq!(index.char_to_utf16(char_idx), Some(units));
+            assert_eq!(index.utf16_to_char(units), Some(char_idx));
+            units += c.len_utf16();
+        }
+
+        assert_eq!(index.utf16_to_char(units), Some(index.char_count()));
+    }
+
+    let mut index = OwnedIndexedChars::with_utf16_index("a💯ö".repeat(100));
+    check(&index);
+
+    index.push('💯');
+    index.push_str("föo💯");
+    check(&index);
+
+    index.insert_char(3, '💯');
+    assert_eq!(index.remove_char(1), Some('💯'));
+    check(&index);
+
+    index.replace_range_chars(10..20, "💯b💯");
+    index.truncate_chars(150);
+    check(&index);
+
+    index.retain_chars(|c| c != 'ö');
+    check(&index);
+
+    let index = index.map_buf(|s| s.replace('a', "💯"));
+    check(&index);
+
+    let sliced = index
+        .as_indexed()
+        .char_slice(5..50)
+        .unwrap()
+        .to_owned_indexed();
+    check(&sliced);
+
+    let mut ascii = OwnedIndexedChars::with_utf16_index(String::from("foo"));
+    ascii.push('💯');
+    check(&ascii);
+    ascii.truncate_chars(2);
+    ascii.push('ö');
+    check(&ascii);
+}
+
+#[cfg(test)]
+fn check_reindex<O: OffsetInt>(s: &str, char_idx: usize, remove: usize, insert: &str) {
+    use alloc::string::String;
+
+    let mut index = IndexedCharsInner::<O>::new(s);
+
+    let start = index.char_boundary(s, char_idx).unwrap();
+    let end = index.char_boundary(s, char_idx + remove).unwrap();
+
+    let mut modified = String::from(s);
+    modified.replace_range(start..end, insert);
+
+    index.reindex_from(&modified, char_idx, start);
+
+    let fresh = IndexedCharsInner::<O>::new(&modified);
+
+    assert_eq!(index.chars, fresh.chars);
+    assert_eq!(index.rollovers, fresh.rollovers);
+    assert_eq!(index.char_len, fresh.char_len);
+}
+
+#[test]
+fn reindex() {
+    let s = alloc::format!("{}foo{}", "ö".repeat(255), "a".repeat(10));
+
+    check_reindex::<u8>(&s, 0, 1, "");
+    check_reindex::<u8>(&s, 100, 50, "");
+    // removing every non ascii char goes back to the ascii niche
+    check_reindex::<u8>(&s, 0, 255, "");
+    check_reindex::<u8>(&s, 254, 1, "");
+    check_reindex::<u8>(&s, 267, 1, "");
+    check_reindex::<u16>(&s, 100, 50, "");
+    check_reindex::<u8>("föo", 1, 1, "");
+}
+
+#[test]
+fn reindex_insert() {
+    let s = alloc::format!("{}foo{}", "ö".repeat(255), "a".repeat(10));
+
+    // inserting a multi byte char before the last 'ö' pushes it over the first rollover
+    check_reindex::<u8>(&s, 254, 0, "💯");
+    check_reindex::<u8>(&s, 0, 0, "💯");
+    check_reindex::<u8>(&s, 258, 0, "ä");
+    // inserting at the end appends
+    check_reindex::<u8>(&s, 268, 0, "ä");
+    check_reindex::<u8>(&s, 10, 0, "bär💯");
+    check_reindex::<u16>(&s, 10, 0, "bär💯");
+    // inserting into an ascii string leaves the ascii niche
+    check_reindex::<u8>("foo", 1, 0, "ö");
+    check_reindex::<u8>("", 0, 0, "ö");
+}
+
+#[test]
+fn to_string() {
+    use crate::{IndexedChars, OwnedIndexedChars};
+    use alloc::string::{String, ToString};
+
+    for s in ["", "foo", "föobär💯"] {
+        let index = IndexedChars::new(s);
+
+        assert_eq!(index.to_string(), s);
+        assert_eq!(String::from(IndexedChars::new(s)), s);
+
+        let owned = OwnedIndexedChars::new(index.to_string());
+
+        assert_eq!(owned.to_string(), s);
+        assert_eq!(String::from(owned), s);
+    }
+}
+
+#[test]
+fn hinted() {
+    use rand::{thread_rng, Rng};
+
+    let s = "föobär💯".repeat(1000);
+    let index = IndexedCharsInner::<u8>::new(&s);
+    let char_count = index.char_count(&s);
+
+    let mut rollover = 0;
+
+    // sequential forwards and backwards, then random jumps
+    let forward = 0..char_count;
+    let backward = (0..char_count).rev();
+    let random = (0..1000).map(|_| thread_rng().gen_range(0..char_count + 10));
+
+    for i in forward.chain(backward).chain(random) {
+        assert_eq!(
+            index.char_to_byte_hinted(&s, i, &mut rollover),
+            index.char_to_byte(&s, i)
+        );
+    }
+
+    let ascii = IndexedCharsInner::<u8>::new("foo");
+
+    assert_eq!(ascii.char_to_byte_hinted("foo", 2, &mut rollover), Some(2));
+    assert_eq!(ascii.char_to_byte_hinted("foo", 3, &mut rollover), None);
+}
+
+#[test]
+fn count_matches() {
+    let s = "föobär💯o".repeat(100);
+
+    for (buf, index) in [
+        (&*s, IndexedCharsInner::<u8>::new(&s)),
+        ("foobar", IndexedCharsInner::new("foobar")),
+    ] {
+        for c in ['o', 'ö', '💯', 'x', '\0'] {
+            assert_eq!(index.count_matches(buf, c), buf.matches(c).count());
+        }
+    }
+}
+
+#[test]
+fn contains_char() {
+    use alloc::format;
+
+    let long = "föobär".repeat(100);
+    // matches in a full chunk, in the remainder, and not at all
+    let x_early = format!("{}x{}", &long[..48], &long[48..]);
+    let x_late = format!("{long}x");
+
+    for buf in [&*long, &*x_early, &*x_late, "foobar", ""] {
+        let index = IndexedCharsInner::<u8>::new(buf);
+
+        for c in ['o', 'ö', 'x', '💯', '\0'] {
+            assert_eq!(index.contains_char(buf, c), buf.contains(c), "{c:?}");
+        }
+    }
+}
+
+#[test]
+fn as_bytes() {
+    use crate::{IndexedChars, OwnedIndexedChars, SharedIndexedChars};
+    use alloc::{rc::Rc, string::String};
+
+    fn bytes<T: AsRef<[u8]>>(t: &T) -> &[u8] {
+        t.as_ref()
+    }
+
+    for s in ["", "foo", "föobär💯"] {
+        assert_eq!(bytes(&IndexedChars::new(s)), s.as_bytes());
+        assert_eq!(
+            bytes(&OwnedIndexedChars::new(String::from(s))),
+            s.as_bytes()
+        );
+        assert_eq!(
+            bytes(&SharedIndexedChars::new(Rc::<str>::from(s))),
+            s.as_bytes()
+        );
+    }
+}
+
+#[test]
+fn from_str_iter() {
+    use crate::OwnedIndexedChars;
+    use alloc::{string::String, vec::Vec};
+
+    let pieces: Vec<&str> = ["foo", "bär", "", "💯"]
+        .iter()
+        .copied()
+        .cycle()
+        .take(400)
+        .collect();
+    let index: OwnedIndexedChars = pieces.iter().copied().collect();
+
+    assert!(index.validate());
+    assert_eq!(index, pieces.concat());
+    assert_eq!(index, OwnedIndexedChars::new(pieces.concat()));
+
+    let empty: OwnedIndexedChars = core::iter::empty::<&str>().collect();
+    assert_eq!(empty, String::new());
+}
+
+#[test]
+fn string_cmp() {
+    use crate::OwnedIndexedChars;
+    use alloc::string::String;
+    use core::cmp::Ordering;
+
+    let index = OwnedIndexedChars::new(String::from("föo"));
+    let same = String::from("föo");
+    let greater = String::from("föp");
+
+    assert_eq!(same, index);
+    assert_eq!(index, same);
+    assert_eq!(index.partial_cmp(&greater), Some(Ordering::Less));
+    assert_eq!(greater.partial_cmp(&index), Some(Ordering::Greater));
+    assert_eq!(same.partial_cmp(&index), Some(Ordering::Equal));
+    assert_eq!(index.partial_cmp(&"fp"), Some(Ordering::Greater));
+    assert_eq!("fp".partial_cmp(&index), Some(Ordering::Less));
+    assert_eq!("föo".partial_cmp(&index), Some(Ordering::Equal));
+}
+
+#[test]
+fn eq() {
+    use crate::{IndexedChars, IndexedChars16, OwnedIndexedChars, SharedIndexedChars};
+    use alloc::{rc::Rc, string::String};
+
+    // same byte length, different char counts
+    assert_ne!(IndexedChars::new("ää"), IndexedChars::new("abcd"));
+    assert_ne!(
+        OwnedIndexedChars::new(String::from("ää")),
+        IndexedChars::new("abcd")
+    );
+    assert_ne!(
+        SharedIndexedChars::new(Rc::<str>::from("ää")),
+        SharedIndexedChars::new(Rc::<str>::from("abcd"))
+    );
+
+    // same char count, different bytes
+    assert_ne!(IndexedChars::new("föo"), IndexedChars::new("fäo"));
+
+    // an index that was truncated back to ascii must still equal a freshly built one
+    let mut owned = OwnedIndexedChars::new(String::from("foobär"));
+    owned.truncate_chars(3);
+
+    assert_eq!(owned, IndexedChars::new("foo"));
+    assert_eq!(IndexedChars::new("foo"), owned);
+    assert_eq!(owned, OwnedIndexedChars::new(String::from("foo")));
+
+    for s in ["", "foo", "föobär💯", &"föobär💯".repeat(100)] {
+        assert_eq!(IndexedChars::new(s), IndexedChars::new(s));
+        assert_eq!(
+            OwnedIndexedChars::new(String::from(s)),
+            IndexedChars16::new_with_width(s)
+        );
+    }
+}
+
+#[test]
+fn map_buf() {
+    use crate::OwnedIndexedChars;
+    use alloc::string::{String, ToString};
+
+    let index = OwnedIndexedChars::new("föobär💯".repeat(100));
+
+    let upper = index.map_buf(|s| s.to_uppercase());
+    assert!(upper.validate());
+    assert_eq!(upper, "FÖOBÄR💯".repeat(100));
+
+    // from non ascii to the ascii niche and back
+    let ascii = upper.map_buf(|s| s.replace(|c: char| !c.is_ascii(), "_"));
+    assert!(ascii.validate());
+    assert!(ascii.is_ascii());
+
+    let back = ascii.map_buf(|s| s.replace('_', "ä"));
+    assert!(back.validate());
+    assert_eq!(back.get_char(1), Some('ä'));
+
+    let empty = back.map_buf(|_| String::new());
+    assert!(empty.validate());
+    assert_eq!(empty.to_string(), "");
+}
+
+#[test]
+fn char_offsets() {
+    use alloc::vec::Vec;
+
+    for s in ["", "foo", "föobär💯", &"föobär💯".repeat(100)] {
+        let index = IndexedCharsInner::<u8>::new(s);
+
+        let offsets: Vec<_> = index.char_offsets().collect();
+        let expected: Vec<_> = s.char_indices().map(|(i, _)| i).collect();
+
+        assert_eq!(offsets, expected);
+    }
+}
+
+#[cfg(feature = "unicode-normalization")]
+#[test]
+fn normalized() {
+    use alloc::borrow::Cow;
+
+    assert!(matches!(normalize_nfc("föo"), Cow::Borrowed("föo")));
+    assert!(matches!(normalize_nfc(""), Cow::Borrowed("")));
+
+    let decomposed = "fo\u{308}o";
+    assert_eq!(normalize_nfc(decomposed), "föo");
+    assert!(matches!(normalize_nfc(decomposed), Cow::Owned(_)));
+}
+
+#[test]
+fn const_ascii() {
+    use crate::IndexedChars;
+
+    static EMPTY: IndexedChars<'static> = IndexedChars::empty();
+    static FOO: Option<IndexedChars<'static>> = IndexedChars::new_ascii("foo");
+
+    assert!(EMPTY.is_empty());
+    assert_eq!(EMPTY.char_count(), 0);
+    assert_eq!(EMPTY, IndexedChars::new(""));
+
+    let foo = FOO.as_ref().unwrap();
+    assert_eq!(foo.get_char(2), Some('o'));
+    assert_eq!(foo.char_count(), 3);
+    assert_eq!(*foo, IndexedChars::new("foo"));
+
+    assert!(IndexedChars::new_ascii("föo").is_none());
+    assert!(IndexedChars::new_ascii("foo\u{7f}").is_some());
+}
+
+#[test]
+fn retain() {
+    use crate::OwnedIndexedChars;
+    use alloc::string::String;
+
+    fn check_retain(s: &str, f: impl Fn(char) -> bool) {
+        let mut index = OwnedIndexedChars::new(String::from(s));
+        index.retain_chars(&f);
+
+        let expected: String = s.chars().filter(|&c| f(c)).collect();
+
+        assert!(index.validate());
+        assert_eq!(index, OwnedIndexedChars::new(expected));
+    }
+
+    let s = "föobär💯".repeat(200);
+    assert!(OwnedIndexedChars::new(s.clone()).rollover_count() > 0);
+
+    // dropping every multi byte char removes all rollovers and returns to the ascii niche
+    check_retain(&s, |c| c.is_ascii());
+    // dropping only some keeps fewer rollovers
+    check_retain(&s, |c| c != '💯');
+    check_retain(&s, |c| c != 'o');
+    check_retain(&s, |_| true);
+    check_retain(&s, |_| false);
+    check_retain("foobar", |c| c != 'o');
+    check_retain("", |_| true);
+
+    // many multi byte chars after an ascii prefix triggers rollovers while retaining
+    let mixed = "foobar".repeat(100) + &"💯x".repeat(200);
+    check_retain(&mixed, |c| c != 'x');
+}
+
+#[test]
+fn replace_range() {
+    use crate::OwnedIndexedChars;
+    use alloc::string::String;
+
+    fn check_replace(s: &str, range: Range<usize>, replacement: &str) {
+        let mut index = OwnedIndexedChars::new(String::from(s));
+        index.replace_range_chars(range.clone(), replacement);
+
+        let mut expected: String = s.chars().take(range.start).collect();
+        expected.push_str(replacement);
+        expected.extend(s.chars().skip(range.end));
+
+        assert!(index.validate());
+        assert_eq!(index, OwnedIndexedChars::new(expected));
+    }
+
+    let s = "föobär💯".repeat(200);
+
+    // growing and shrinking spans before rollovers shift every rollover after them
+    check_replace(&s, 0..0, &"💯".repeat(100));
+    check_replace(&s, 1..2, "o");
+    check_replace(&s, 5..600, "");
+    check_replace(&s, 100..1400, "föo");
+    check_replace(&s, 1400..1400, "ä");
+    check_replace(&s, 0..1400, "foo");
+    check_replace("foobar", 2..4, "öö");
+    check_replace("foobar", 0..6, "");
+    check_replace("", 0..0, "föo");
+}
+
+#[test]
+fn exact_size() {
+    use crate::IndexedChars;
+    use alloc::{string::String, vec::Vec};
+
+    /// Drains an iterator from both ends, checking its reported length is exact before every step
+    fn check_exact<I: DoubleEndedIterator + ExactSizeIterator>(mut iter: I, len: usize) {
+        for remaining in (0..=len).rev() {
+            assert_eq!(iter.len(), remaining);
+            assert_eq!(iter.size_hint(), (remaining, Some(remaining)));
+
+            let next = if remaining % 2 == 0 {
+                iter.next()
+            } else {
+                iter.next_back()
+            };
+
+            assert_eq!(next.is_some(), remaining != 0);
+        }
+    }
+
+    /// Drains an iterator from the front, checking its reported length is exact before every step
+    fn check_exact_forward<I: ExactSizeIterator>(mut iter: I, len: usize) {
+        for remaining in (0..=len).rev() {
+            assert_eq!(iter.size_hint(), (remaining, Some(remaining)));
+            assert_eq!(iter.next().is_some(), remaining != 0);
+        }
+    }
+
+    for s in ["", "foo", "föobär💯", &"föobär💯".repeat(100)] {
+        let index = IndexedChars::new(s);
+        let len = index.len_chars();
+
+        check_exact(index.chars(), len);
+        check_exact(index.rchars(), len);
+        check_exact(index.char_indices(), len);
+        check_exact(index.rchar_indices(), len);
+
+        for start in [0, len / 2, len] {
+            check_exact(index.chars_from(start).unwrap(), len - start);
+        }
+
+        for size in [1, 2, 7] {
+            check_exact_forward(index.char_windows(size), (len + 1).saturating_sub(size));
+            check_exact_forward(index.char_chunks(size), (len + size - 1) / size);
+        }
+
+        // sizes this large used to overflow while computing the amount of chunks
+        check_exact_forward(index.char_windows(usize::MAX), 0);
+        check_exact_forward(index.char_chunks(usize::MAX), usize::from(len != 0));
+
+        // collecting presizes from the exact length, Vec rounds allocations of fewer than 4 chars up to 4
+        let chars: Vec<char> = index.chars().collect();
+        if len >= 4 {
+            assert_eq!(chars.capacity(), len);
+        }
+        assert_eq!(index.chars().collect::<String>(), s);
+    }
+}
+
+/// Generates strings made of runs of a single char, long runs of multi byte chars roll over u8 offsets many times,
+/// and runs ending at arbitrary lengths put the rollovers at arbitrary places around the ascii chars between them
+#[cfg(test)]
+fn runs() -> impl proptest::strategy::Strategy<Value = alloc::string::String> {
+    use proptest::{arbitrary::any, collection, strategy::Strategy};
+
+    collection::vec((any::<char>(), 0..400_usize), 0..12).prop_map(|runs| {
+        runs.into_iter()
+            .flat_map(|(c, len)| iter::repeat(c).take(len))
+            .collect()
+    })
+}
+
+/// Checks every lookup against `chars().nth`, the oracle is stepped along instead of restarted for each index to keep this O(n)
+#[cfg(test)]
+fn check_oracle<O: OffsetInt>(s: &str) {
+    let index = IndexedCharsInner::<O>::new(s);
+    let mut oracle = s.chars();
+
+    for i in 0..=s.chars().count() {
+        assert_eq!(index.get_char(s, i), oracle.next(), "index {i} of {s:?}");
+    }
+}
+
+#[cfg(test)]
+proptest::proptest! {
+    #[test]
+    fn get_char_matches_nth(s in proptest::arbitrary::any::<alloc::string::String>()) {
+        check_oracle::<u8>(&s);
+    }
+
+    #[test]
+    fn get_char_matches_nth_runs(s in runs()) {
+        check_oracle::<u8>(&s);
+        check_oracle::<u16>(&s);
+    }
+
+    #[test]
+    fn get_char_matches_nth_astral(
+        chars in proptest::collection::vec(proptest::char::range('\u{10000}', '\u{10FFFF}'), 0..500)
+    ) {
+        let s: alloc::string::String = chars.iter().collect();
+
+        check_oracle::<u8>(&s);
+    }
+}
+
+#[test]
+fn rollover_boundaries() {
+    use alloc::{string::String, vec};
+
+    fn check<O: OffsetInt>(s: &str) -> IndexedCharsInner<O> {
+        let index = IndexedCharsInner::<O>::new(s);
+
+        for (i, (byte_idx, c)) in s.char_indices().enumerate() {
+            assert_eq!(index.get_char(s, i), Some(c), "char {i}");
+            assert_eq!(index.char_to_byte(s, i), Some(byte_idx), "char {i}");
+            assert_eq!(index.byte_to_char(s, byte_idx), Some(i), "byte {byte_idx}");
+        }
+
+        assert_eq!(index.get_char(s, s.chars().count()), None);
+
+        index
+    }
+
+    // every 'ö' grows the offset by exactly one, so char i has offset i, landing on 255, 256, 510, 511 and so on
+    let two_byte = "ö".repeat(1000);
+    let index = check::<u8>(&two_byte);
+
+    // offset 255 is still stored as is, 256 is the first to roll over, then every 255 after that
+    assert_eq!(index.chars[255], 255);
+    assert_eq!(index.chars[256], 1);
+    assert_eq!(index.rollovers, vec![256, 511, 766]);
+
+    // a 4 byte char grows the offset by 3, so 85 of them reach exactly 255 and the 86th jumps over it
+    let four_byte = "💯".repeat(200);
+    let index = check::<u8>(&four_byte);
+
+    assert_eq!(index.chars[85], 255);
+    assert_eq!(index.rollovers[0], 86);
+
+    // ascii chars between them do not grow the offset, char i follows (i + 1) / 2 of the 'ö's here,
+    // so the offset passes 255 at char 511 and 510 at char 1021
+    let mixed: String = (0..1200)
+        .map(|i| if i % 2 == 0 { "ö" } else { "a" })
+        .collect();
+    let index = check::<u8>(&mixed);
+
+    assert_eq!(index.rollovers, vec![511, 1021]);
+
+    // u16 offsets land on their own boundary the same way
+    let wide = "ö".repeat(65_536 + 10);
+    let index = check::<u16>(&wide);
+
+    assert_eq!(index.chars[65_535], 65_535);
+    assert_eq!(index.rollovers, vec![65_536]);
+}
+
+#[test]
+fn char_count_hint() {
+    for s in ["", "foo", "föobär💯", &"föobär💯".repeat(100)] {
+        let hinted = IndexedCharsInner::<u8>::new_with_char_count(s, s.chars().count());
+        let index = IndexedCharsInner::<u8>::new(s);
+
+        assert_eq!(hinted.chars, index.chars);
+        assert_eq!(hinted.rollovers, index.rollovers);
+        assert_eq!(hinted.char_len, index.char_len);
+    }
+}
+
+#[test]
+fn count_in_range() {
+    let s = "föobär💯".repeat(100);
+    let index = IndexedCharsInner::<u8>::new(&s);
+
+    let boundaries: alloc::vec::Vec<usize> = s
+        .char_indices()
+        .map(|(i, _)| i)
+        .chain(iter::once(s.len()))
+        .collect();
+
+    for start in (0..boundaries.len()).step_by(7) {
+        for end in (start..boundaries.len()).step_by(13) {
+            let range = boundaries[start]..boundaries[end];
+
+            assert_eq!(
+                index.char_count_in_range(&s, range.clone()),
+                Some(s[range].chars().count())
+            );
+        }
+    }
+
+    // inside 'ö', reversed, and past the end
+    assert_eq!(index.char_count_in_range(&s, 2..4), None);
+    assert_eq!(
+        index.char_count_in_range(&s, Range { start: 3, end: 1 }),
+        None
+    );
+    assert_eq!(index.char_count_in_range(&s, 0..s.len() + 1), None);
+}
+
+#[test]
+fn line_col() {
+    let s = "föo\nbär💯\n\nbaz\n".repeat(50);
+    let index = IndexedCharsInner::<u8>::new(&s);
+
+    let (mut line, mut column) = (0, 0);
+
+    for (i, c) in s.chars().enumerate() {
+        assert_eq!(index.line_col(&s, i), Some((line, column)));
+
+        if c == '\n' {
+            line += 1;
+            column = 0;
+        } else {
+            column += 1;
+        }
+    }
+
+    let len = s.chars().count();
+
+    assert_eq!(index.line_col(&s, len), Some((line, column)));
+    assert_eq!(index.line_col(&s, len + 1), None);
+    assert_eq!(
+        IndexedCharsInner::<u8>::new("").line_col("", 0),
+        Some((0, 0))
+    );
+}
+
+#[cfg(feature = "unicode-width")]
+#[test]
+fn width() {
+    let s = "a中b\u{301}文😀\n";
+    let index = IndexedCharsInner::<u8>::new(s);
+
+    // a, 中 (2), b with a combining accent (1), 文 (2), 😀 (2), and a newline (0)
+    assert_eq!(display_width(s), 8);
+
+    let expected = [0, 1, 1, 2, 4, 4, 5, 5];
+
+    for (column, &char_idx) in expected.iter().enumerate() {
+        assert_eq!(
+            index.width_to_char(s, column),
+            Some(char_idx),
+            "column {column}"
+        );
+    }
+
+    assert_eq!(index.width_to_char(s, 8), Some(7));
+    assert_eq!(index.width_to_char(s, 9), None);
+}
+
+#[test]
+fn reversed() {
+    use crate::{IndexedChars, OwnedIndexedChars};
+    use alloc::string::String;
+
+    for s in ["", "foo", "föobär💯", &"föobär💯".repeat(100)] {
+        let reversed = IndexedChars::new(s).reversed();
+        let expected: String = s.chars().rev().collect();
+
+        assert!(reversed.validate());
+        assert_eq!(reversed, OwnedIndexedChars::new(expected));
+        assert_eq!(reversed.reversed(), s);
+    }
+}
+
+#[test]
+fn split_char_indices() {
+    use crate::IndexedChars;
+
+    for s in ["", ",", "föo", ",föo,,bär💯,", &"föo,bär💯,,".repeat(50)] {
+        let index = IndexedChars::new(s);
+
+        assert!(index
+            .split_char_indices(',')
+            .map(|(_, seg)| seg)
+            .eq(s.split(',')));
+
+        for (char_idx, segment) in index.split_char_indices(',') {
+            let start = index.char_to_byte(char_idx).unwrap_or(s.len());
+
+            assert!(s[start..].starts_with(segment));
+            assert!(start == 0 || index.nth_char_is(char_idx - 1, ','));
+        }
+    }
+}
+
+#[test]
+fn get_at() {
+    use crate::{IndexedChars, OwnedIndexedChars};
+    use alloc::string::String;
+
+    let s = "föobär";
+    let index = IndexedChars::new(s);
+
+    assert_eq!(index.get_at(4), Some('ä'));
+    assert_eq!(index.get_at(6), None);
+    assert_eq!(index.get_at(2..4), Some("ob"));
+    assert_eq!(index.get_at(2..=4), Some("obä"));
+    assert_eq!(index.get_at(3..), Some("bär"));
+    assert_eq!(index.get_at(..2), Some("fö"));
+    assert_eq!(index.get_at(..), Some(s));
+    assert_eq!(index.get_at(..=usize::MAX), None);
+
+    let owned = OwnedIndexedChars::new(String::from(s));
+    assert_eq!(owned.get_at(1..=1), Some("ö"));
+}
+
+#[test]
+fn trim_indexed() {
+    use crate::IndexedChars;
+
+    let index = IndexedChars::new("\u{3000} föö\tbär \n");
+    let trimmed = index.trim_indexed();
+    assert_eq!(trimmed, "föö\tbär");
+    assert_eq!(trimmed.char_count(), 7);
+    assert_eq!(trimmed.get_char(5), Some('ä'));
+
+    assert_eq!(index.trim_start_indexed().get_char(0), Some('f'));
+    assert_eq!(index.trim_end_indexed().char_count(), 9);
+
+    let blank = IndexedChars::new(" \u{3000}\n");
+    assert_eq!(blank.trim_indexed(), "");
+    assert_eq!(blank.trim_start_indexed(), "");
+    assert_eq!(blank.trim_end_indexed(), "");
+}
+
+#[test]
+fn rollover_arithmetic() {
+    // pushes offsets on both sides of every multiple of O::MAX, as the largest strings would reach,
+    // and checks that lookups add the stored offsets and rollovers back up to the same byte index
+    fn check<O: OffsetInt>() {
+        let offsets: Vec<usize> = (0..4).flat_map(|k| [k * O::MAX, k * O::MAX + 1]).collect();
+
+        let mut index = IndexedCharsInner::<O> {
+            chars: Vec::new(),
+            rollovers: Vec::new(),
+            char_len: offsets.len(),
+            astral: None,
+        };
+
+        for (char_idx, &offset) in offsets.iter().enumerate() {
+            index.push_offset(char_idx, offset);
+        }
+
+        for (char_idx, &offset) in offsets.iter().enumerate() {
+            let stored = index.chars[char_idx].to_usize();
+
+            assert!(stored <= O::MAX);
+            assert_eq!(stored + O::MAX * index.rollovers_until(char_idx), offset);
+            // the string is only read for ascii indices, which this is not
+            assert_eq!(index.char_to_byte("", char_idx), Some(char_idx + offset));
+        }
+
+        assert_eq!(index.char_to_byte("", offsets.len()), None);
+    }
+
+    check::<u8>();
+    // the offsets past O::MAX only fit in usize when O is narrower than it
+    #[cfg(not(target_pointer_width = "16"))]
+    check::<u16>();
+    #[cfg(target_pointer_width = "64")]
+    check::<u32>();
+}
+
+#[test]
+fn max_len() {
+    // the longest string rust can allocate, on any target
+    const MAX_LEN: usize = isize::MAX as usize;
+
+    // the last char of the worst case string is too far in to push for real, so this runs the arithmetic
+    // push_offset and char_to_byte use for it, any overflow panics as tests are built with overflow checks
+    fn check<O: OffsetInt>() {
+        // a string of only 4 byte chars has the largest offsets
+        let last = MAX_LEN / 4 - 1;
+        let offset = last * 3;
+
+        // the char before the last one has already pushed its rollovers
+        let (_, before) = IndexedCharsInner::<O>::split_offset(offset - 3, 0);
+        let (stored, new_rollovers) = IndexedCharsInner::<O>::split_offset(offset, before);
+        assert!(stored <= O::MAX);
+
+        let rollovers = before + new_rollovers;
+        assert_eq!(
+            last + IndexedCharsInner::<O>::join_offset(stored, rollovers),
+            last * 4
+        );
+    }
+
+    check::<u8>();
+    check::<u16>();
+    #[cfg(not(target_pointer_width = "16"))]
+    check::<u32>();
+}
+
+#[cfg(feature = "unsafe")]
+#[test]
+#[allow(unsafe_code)]
+fn shared_unchecked() {
+    use crate::SharedIndexedChars;
+    use alloc::rc::Rc;
+    use core::{cell::Cell, ops::Deref};
+
+    /// A string pointer that derefs to a different string every time
+    struct Flip(Cell<bool>);
+
+    impl Deref for Flip {
+        type Target = str;
+
+        fn deref(&self) -> &str {
+            self.0.set(!self.0.get());
+
+            if self.0.get() {
+                "ééééé"
+            } else {
+                ""
+            }
+        }
+    }
+
+    let stable = SharedIndexedChars::new(Rc::<str>::from("föo"));
+    // SAFETY: 1 is below the char count
+    assert_eq!(unsafe { stable.as_indexed().get_char_unchecked(1) }, 'ö');
+
+    let flip = SharedIndexedChars::new(Flip(Cell::new(false)));
+    let view = flip.as_indexed();
+    assert_eq!(view.char_count(), 5);
+    assert_eq!(view.len(), 0);
+
+    // the index no longer matches the string, which must panic rather than read out of bounds
+    let result = std::panic::catch_unwind(core::panic::AssertUnwindSafe(|| {
+        // SAFETY: 4 is below the char count, which is all the caller has to guarantee
+        unsafe { view.get_char_unchecked(4) }
+    }));
+    assert!(result.is_err());
+
+    // copies of an unverified view rebuild their index rather than trusting it
+    assert_eq!(view.to_owned_indexed(), "");
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "wrong char count hint")]
+fn wrong_char_count_hint_debug() {
+    let _ = IndexedCharsInner::<u8>::new_with_char_count("föo", 2);
+}
+
+#[test]
+fn wrong_char_count_hint() {
+    // new_with_char_count without its debug assertion, as release builds run it
+    for (s, hint) in [
+        ("abc", 2),
+        ("abc", 7),
+        ("föo", 3),
+        ("föo", 4),
+        ("föo", 2),
+        ("föo", 9),
+    ] {
+        let index = IndexedCharsInner::<u8>::new_counted_in(
+            s,
+            Vec::new(),
+            Vec::new(),
+            niche_char_count(s, hint),
+        );
+
+        assert!(index.validate(s), "{s:?} {hint}");
+        assert_eq!(index.is_ascii(), s.is_ascii());
+        assert_eq!(index.char_count(s), s.chars().count());
+
+        // an ascii string stays in the niche and allocates nothing
+        if s.is_ascii() {
+            assert_eq!(index.memory_footprint(), 0);
+        }
+    }
+
+    // new_counted_in on its own still builds a correct index from a wrong count for a non ascii string
+    for hint in [0, 1, 2, 9] {
+        let index = IndexedCharsInner::<u8>::new_counted_in("föo", Vec::new(), Vec::new(), hint);
+
+        assert!(index.validate("föo"), "{hint}");
+    }
 }
@@ -0,0 +1,254 @@
+//! Module containing [`InlineIndexedChars`] and its trait implementations
+
+use core::{
+    borrow::Borrow,
+    cmp::{Eq, PartialEq},
+    fmt,
+    hash::{Hash, Hasher},
+    ops::Deref,
+};
+
+use crate::IndexedCharsInner;
+
+/// Storage of the char offsets of an [`InlineIndexedChars`].
+#[derive(Clone)]
+enum Storage<const N: usize> {
+    /// Offsets of a short non ascii string, stored without allocating.
+    /// Only used when every offset fits in a u8 without rolling over.
+    Inline {
+        /// The offset of each char, only the first `char_len` are used
+        offsets: [u8; N],
+        /// The amount of chars in the string
+        char_len: usize,
+    },
+    /// Index of a string too long to fit inline, or of an ascii string, which is the ascii niche and does not allocate either
+    Heap(IndexedCharsInner<u8>),
+}
+
+/// A string whose char indices have been cached for ~O(1) char lookup, storing the offsets of short strings inline.
+///
+/// This is the same as [`IndexedChars`](crate::IndexedChars), but the offsets of non ascii strings of up to `N` chars
+/// are stored in an array inside the struct, so short strings are indexed without allocating.
+/// Longer strings spill to the same heap allocated index [`IndexedChars`](crate::IndexedChars) uses,
+/// and ascii strings of any length use the ascii niche, which never allocates.
+///
+/// Lookups read from either storage transparently, [`is_inline`][InlineIndexedChars::is_inline] reports which is in use.
+/// The inline array takes `N` bytes whether it is used or not, so `N` should be picked to fit the typical string.
+///
+/// This type mimics a `&'a str` with its trait impls, including `Debug`, `Display`, `PartialEq` with `&str`, `Hash`, and `AsRef`/`Borrow`.
+///
+/// # Examples
+/// ```rust
+/// # use char_index::InlineIndexedChars;
+/// let index = InlineIndexedChars::<16>::new("föobär");
+///
+/// assert!(index.is_inline());
+/// assert_eq!(index.memory_footprint(), 0);
+/// assert_eq!(index.get_char(4), Some('ä'));
+///
+/// let long = "föobär".repeat(10);
+/// let spilled = InlineIndexedChars::<16>::new(&long);
+///
+/// assert!(!spilled.is_inline());
+/// assert_eq!(spilled.get_char(58), Some('ä'));
+/// ```
+#[derive(Clone)]
+pub struct InlineIndexedChars<'a, const N: usize> {
+    /// Backing string buffer
+    buf: &'a str,
+    /// Char offsets, inline or on the heap
+    storage: Storage<N>,
+}
+
+impl<'a, const N: usize> InlineIndexedChars<'a, N> {
+    /// Constructs a new [`InlineIndexedChars`] instance from a [`&str`]. This is O(n), but the cost should only be paid once ideally.
+    #[must_use]
+    pub fn new(s: &'a str) -> Self {
+        let storage =
+            Self::index_inline(s).unwrap_or_else(|| Storage::Heap(IndexedCharsInner::new(s)));
+
+        Self { buf: s, storage }
+    }
+
+    /// Computes the inline offsets of a string, or returns None if the string is ascii or does not fit inline.
+    fn index_inline(s: &str) -> Option<Storage<N>> {
+        // a string of more than N * 4 bytes has more than N chars, which skips the scan for long strings
+        if s.is_ascii() || s.len() > N * 4 {
+            return None;
+        }
+
+        let mut offsets = [0; N];
+        let mut char_len = 0;
+
+        for (char_idx, (byte_idx, _)) in s.char_indices().enumerate() {
+            // too many chars, or the offset would roll over
+            let offset = offsets.get_mut(char_idx)?;
+            *offset = u8::try_from(byte_idx - char_idx).ok()?;
+            char_len += 1;
+        }
+
+        Some(Storage::Inline { offsets, char_len })
+    }
+
+    /// Returns `true` if the offsets are stored inline, and `false` if they are stored on the heap or the string is ascii.
+    #[must_use]
+    pub fn is_inline(&self) -> bool {
+        matches!(self.storage, Storage::Inline { .. })
+    }
+
+    /// Indexes into the backing string to retrieve the nth codepoint.
+    ///
+    /// This operation is O(1) for inline and ascii strings, and has an average case of O(1) and a worst case of O(log n) otherwise.
+    #[must_use]
+    pub fn get_char(&self, index: usize) -> Option<char> {
+        let byte_index = self.char_to_byte(index)?;
+
+        self.buf[byte_index..].chars().next()
+    }
+
+    /// Converts a char index into the byte index where that char starts in the backing string.
+    ///
+    /// Returns `None` if the index is out of bounds.
+    #[must_use]
+    pub fn char_to_byte(&self, index: usize) -> Option<usize> {
+        match &self.storage {
+            Storage::Inline { offsets, char_len } => {
+                if index < *char_len {
+                    Some(index + usize::from(offsets[index]))
+                } else {
+                    None
+                }
+            }
+            Storage::Heap(inner) => inner.char_to_byte(self.buf, index),
+        }
+    }
+
+    /// Returns the number of chars in the backing string in O(1).
+    #[must_use]
+    pub fn char_count(&self) -> usize {
+        match &self.storage {
+            Storage::Inline { char_len, .. } => *char_len,
+            Storage::Heap(inner) => inner.char_count(self.buf),
+        }
+    }
+
+    /// Returns the number of chars in the backing string in O(1), this is the same as [`char_count`][InlineIndexedChars::char_count].
+    #[must_use]
+    pub fn len_chars(&self) -> usize {
+        self.char_count()
+    }
+
+    /// Returns `true` if the backing string is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.buf.is_empty()
+    }
+
+    /// Returns the amount of heap memory in bytes used by the index, not counting the backing string.
+    ///
+    /// This is 0 for inline and ascii strings.
+    #[must_use]
+    pub fn memory_footprint(&self) -> usize {
+        match &self.storage {
+            Storage::Inline { .. } => 0,
+            Storage::Heap(inner) => inner.memory_footprint(),
+        }
+    }
+
+    /// Returns a reference to the backing `&str`
+    #[must_use]
+    pub fn as_str(&self) -> &'a str {
+        self.buf
+    }
+}
+
+// The following lines are all trait implementations made to mirror what str does, and be compatible with str
+
+impl<const N: usize> Deref for InlineIndexedChars<'_, N> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.buf
+    }
+}
+
+impl<const N: usize> AsRef<str> for InlineIndexedChars<'_, N> {
+    fn as_ref(&self) -> &str {
+        self
+    }
+}
+
+impl<const N: usize> Borrow<str> for InlineIndexedChars<'_, N> {
+    fn borrow(&self) -> &str {
+        self
+    }
+}
+
+impl<const N: usize> fmt::Debug for InlineIndexedChars<'_, N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        <str as fmt::Debug>::fmt(self.buf, f)
+    }
+}
+
+impl<const N: usize> fmt::Display for InlineIndexedChars<'_, N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        <str as fmt::Display>::fmt(self.buf, f)
+    }
+}
+
+impl<const N: usize> Eq for InlineIndexedChars<'_, N> {}
+
+impl<const N: usize> PartialEq for InlineIndexedChars<'_, N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.buf.eq(other.buf)
+    }
+}
+
+impl<const N: usize> PartialEq<str> for InlineIndexedChars<'_, N> {
+    fn eq(&self, other: &str) -> bool {
+        self.buf.eq(other)
+    }
+}
+
+impl<const N: usize> PartialEq<&str> for InlineIndexedChars<'_, N> {
+    fn eq(&self, other: &&str) -> bool {
+        self.buf.eq(*other)
+    }
+}
+
+impl<const N: usize> Hash for InlineIndexedChars<'_, N> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.buf.hash(state);
+    }
+}
+
+#[test]
+fn inline() {
+    fn check<const N: usize>(s: &str, inline: bool) {
+        let index = InlineIndexedChars::<N>::new(s);
+
+        assert_eq!(index.is_inline(), inline, "{s:?}");
+        assert_eq!(index.char_count(), s.chars().count());
+
+        for (i, (byte_idx, c)) in s.char_indices().enumerate() {
+            assert_eq!(index.get_char(i), Some(c));
+            assert_eq!(index.char_to_byte(i), Some(byte_idx));
+        }
+
+        assert_eq!(index.get_char(index.char_count()), None);
+    }
+
+    check::<8>("föobär", true);
+    check::<6>("föobär", true);
+    check::<5>("föobär", false);
+    check::<8>("foobar", false);
+    check::<8>("", false);
+    check::<0>("ö", false);
+
+    // 100 four byte chars would need offsets past 255, so they spill despite fitting in N
+    let emoji = "💯".repeat(100);
+    check::<128>(&emoji, false);
+    check::<128>(&emoji[..85 * 4], true);
+    check::<128>(&emoji[..86 * 4], true);
+    check::<128>(&emoji[..87 * 4], false);
+}
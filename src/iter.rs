@@ -0,0 +1,291 @@
+//! Module containing the iterator types returned by [`IndexedChars`](crate::IndexedChars) and [`OwnedIndexedChars`](crate::OwnedIndexedChars)
+
+use core::{iter::FusedIterator, str};
+
+/// An iterator over the chars of an indexed string, in order.
+///
+/// Unlike [`str::Chars`], this iterator knows exactly how many chars remain thanks to the cached char count,
+/// and thus implements [`ExactSizeIterator`].
+///
+/// This struct is created by the `chars` method on [`IndexedChars`](crate::IndexedChars) and [`OwnedIndexedChars`](crate::OwnedIndexedChars).
+#[derive(Debug, Clone)]
+pub struct Chars<'a> {
+    /// Backing char iterator, walks the string directly
+    iter: str::Chars<'a>,
+    /// Amount of chars not yet yielded from either end
+    remaining: usize,
+}
+
+impl<'a> Chars<'a> {
+    /// Creates a new [`Chars`] iterator from a string and its char count,
+    /// the count passed must be the exact number of chars in the string.
+    pub(crate) fn new(buf: &'a str, char_count: usize) -> Self {
+        Self {
+            iter: buf.chars(),
+            remaining: char_count,
+        }
+    }
+
+    /// Returns the remaining chars of the iterator as a string slice.
+    #[must_use]
+    pub fn as_str(&self) -> &'a str {
+        self.iter.as_str()
+    }
+}
+
+impl Iterator for Chars<'_> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.iter.next()?;
+        self.remaining -= 1;
+        Some(c)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+
+    fn count(self) -> usize {
+        self.remaining
+    }
+
+    fn last(mut self) -> Option<char> {
+        self.next_back()
+    }
+}
+
+impl DoubleEndedIterator for Chars<'_> {
+    fn next_back(&mut self) -> Option<char> {
+        let c = self.iter.next_back()?;
+        self.remaining -= 1;
+        Some(c)
+    }
+}
+
+impl ExactSizeIterator for Chars<'_> {}
+
+impl FusedIterator for Chars<'_> {}
+
+/// An iterator over the chars of an indexed string and their positions, in order.
+///
+/// Items are yielded as `(char_index, byte_index, char)`, where `char_index` is the same index
+/// that would be passed to `get_char` to retrieve `char`, and `byte_index` is its offset in the backing string.
+///
+/// This struct is created by the `char_indices` method on [`IndexedChars`](crate::IndexedChars) and [`OwnedIndexedChars`](crate::OwnedIndexedChars).
+#[derive(Debug, Clone)]
+pub struct CharIndices<'a> {
+    /// Backing char indices iterator, provides byte offsets
+    iter: str::CharIndices<'a>,
+    /// Char index of the next char yielded from the front
+    front: usize,
+    /// Char index one past the next char yielded from the back
+    back: usize,
+}
+
+impl<'a> CharIndices<'a> {
+    /// Creates a new [`CharIndices`] iterator from a string and its char count,
+    /// the count passed must be the exact number of chars in the string.
+    pub(crate) fn new(buf: &'a str, char_count: usize) -> Self {
+        Self {
+            iter: buf.char_indices(),
+            front: 0,
+            back: char_count,
+        }
+    }
+
+    /// Returns the remaining chars of the iterator as a string slice.
+    #[must_use]
+    pub fn as_str(&self) -> &'a str {
+        self.iter.as_str()
+    }
+}
+
+impl Iterator for CharIndices<'_> {
+    type Item = (usize, usize, char);
+
+    fn next(&mut self) -> Option<(usize, usize, char)> {
+        let (byte_idx, c) = self.iter.next()?;
+        let char_idx = self.front;
+        self.front += 1;
+        Some((char_idx, byte_idx, c))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+
+    fn count(self) -> usize {
+        self.back - self.front
+    }
+
+    fn last(mut self) -> Option<(usize, usize, char)> {
+        self.next_back()
+    }
+}
+
+impl DoubleEndedIterator for CharIndices<'_> {
+    fn next_back(&mut self) -> Option<(usize, usize, char)> {
+        let (byte_idx, c) = self.iter.next_back()?;
+        self.back -= 1;
+        Some((self.back, byte_idx, c))
+    }
+}
+
+impl ExactSizeIterator for CharIndices<'_> {}
+
+impl FusedIterator for CharIndices<'_> {}
+
+/// Returns the length in bytes of the char starting at `byte_idx` in `buf`, which must be a char boundary.
+fn char_len_at(buf: &str, byte_idx: usize) -> usize {
+    // explicitly unwrap here because a None indicates a bug on our end
+    buf[byte_idx..].chars().next().unwrap().len_utf8()
+}
+
+/// An iterator over overlapping windows of chars of an indexed string, yielded as string slices.
+///
+/// Windows slide forward one char at a time, and only full windows are yielded, matching [`slice::windows`].
+///
+/// This struct is created by the `char_windows` method on [`IndexedChars`](crate::IndexedChars) and [`OwnedIndexedChars`](crate::OwnedIndexedChars).
+#[derive(Debug, Clone)]
+pub struct CharWindows<'a> {
+    /// Backing string being windowed
+    buf: &'a str,
+    /// Byte index of the start of the next window
+    start: usize,
+    /// Byte index of the end of the next window
+    end: usize,
+    /// Amount of windows not yet yielded
+    remaining: usize,
+}
+
+impl<'a> CharWindows<'a> {
+    /// Creates a new [`CharWindows`] iterator from a string, its char count, the window size in chars,
+    /// and the byte index where the first window ends, which is None if the string is shorter than one window.
+    pub(crate) fn new(
+        buf: &'a str,
+        char_count: usize,
+        size: usize,
+        first_end: Option<usize>,
+    ) -> Self {
+        match first_end {
+            Some(end) => Self {
+                buf,
+                start: 0,
+                end,
+                remaining: char_count - size + 1,
+            },
+            None => Self {
+                buf,
+                start: 0,
+                end: 0,
+                remaining: 0,
+            },
+        }
+    }
+}
+
+impl<'a> Iterator for CharWindows<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let window = &self.buf[self.start..self.end];
+        self.remaining -= 1;
+
+        // slide both ends forward by one char, there are no more chars to slide over after the last window
+        if self.remaining != 0 {
+            self.start += char_len_at(self.buf, self.start);
+            self.end += char_len_at(self.buf, self.end);
+        }
+
+        Some(window)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+
+    fn count(self) -> usize {
+        self.remaining
+    }
+}
+
+impl ExactSizeIterator for CharWindows<'_> {}
+
+impl FusedIterator for CharWindows<'_> {}
+
+/// An iterator over non overlapping chunks of chars of an indexed string, yielded as string slices.
+///
+/// The last chunk is shorter if the char count is not a multiple of the chunk size, matching [`slice::chunks`].
+///
+/// This struct is created by the `char_chunks` method on [`IndexedChars`](crate::IndexedChars) and [`OwnedIndexedChars`](crate::OwnedIndexedChars).
+#[derive(Debug, Clone)]
+pub struct CharChunks<'a> {
+    /// Part of the backing string not yet yielded
+    rest: &'a str,
+    /// Amount of chars in each chunk
+    size: usize,
+    /// Amount of chunks not yet yielded
+    remaining: usize,
+}
+
+impl<'a> CharChunks<'a> {
+    /// Creates a new [`CharChunks`] iterator from a string, its char count, and the chunk size in chars,
+    /// the chunk size must not be 0.
+    pub(crate) fn new(buf: &'a str, char_count: usize, size: usize) -> Self {
+        Self {
+            rest: buf,
+            size,
+            // rounds up without adding to char_count, which could overflow for huge chunk sizes
+            remaining: char_count / size + usize::from(char_count % size != 0),
+        }
+    }
+}
+
+impl<'a> Iterator for CharChunks<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        self.remaining -= 1;
+
+        // the last chunk takes whatever is left
+        let mid = if self.remaining == 0 {
+            self.rest.len()
+        } else {
+            // walk forward from the end of the previous chunk, so each char is only stepped over once
+            let mut mid = 0;
+
+            for _ in 0..self.size {
+                mid += char_len_at(self.rest, mid);
+            }
+
+            mid
+        };
+
+        let (chunk, rest) = self.rest.split_at(mid);
+        self.rest = rest;
+
+        Some(chunk)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+
+    fn count(self) -> usize {
+        self.remaining
+    }
+}
+
+impl ExactSizeIterator for CharChunks<'_> {}
+
+impl FusedIterator for CharChunks<'_> {}
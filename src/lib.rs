@@ -1,12 +1,16 @@
 #![no_std]
-#![forbid(unsafe_code)]
+#![cfg_attr(not(feature = "unsafe"), forbid(unsafe_code))]
+#![cfg_attr(feature = "unsafe", deny(unsafe_code))]
 #![warn(clippy::pedantic, clippy::missing_docs_in_private_items, missing_docs)]
 #![warn(clippy::alloc_instead_of_core, clippy::std_instead_of_alloc)]
 #![allow(clippy::module_name_repetitions)]
 //! # `char_index`
 //! A crate that provides a tradeoff of space efficiency and apparent O(1) charwise indexing.  
 //!
-//! To get started, create a new [`IndexedChars`] or [`OwnedIndexedChars`] instance.
+//! To get started, create a new [`IndexedChars`] or [`OwnedIndexedChars`] instance,
+//! or a [`SharedIndexedChars`] for strings behind a pointer such as `Arc<str>`.
+//! Strings built with `write!` can be indexed as they are written with an [`IndexedWriter`],
+//! and strings that arrive in chunks as they are pushed with an [`IndexBuilder`].
 //!
 //! # How it Works
 //! [`IndexedChars`] works by allocating a `Vec<u8>` under the hood that stores char
@@ -24,14 +28,99 @@
 //!
 //! Additionally, as a niche optimization, if the string contains only ascii (all offsets 0); it will simply not allocate any extra memory, and gain perfect O(1) lookup.
 //!
+//! The offsets do not have to be stored as `u8`, any [`OffsetInt`] can be used instead, such as with [`IndexedChars16`].
+//! A wider offset type uses more memory per char, but only rolls over once its own maximum is exceeded, which keeps lookups closer to O(1) for strings that are mostly non ascii.
+//!
+//! # Limits
+//! Strings of any length up to `isize::MAX` bytes, the most a single allocation can hold, can be indexed with every offset type the target supports,
+//! which is all of them apart from `u32` on 16 bit targets, where `IndexedChars32` and `OwnedIndexedChars32` do not exist.
+//! Offsets and rollovers only ever add up to the byte index of a char, so none of the index arithmetic can overflow.
+//!
+//! # Thread Safety
+//! All of the indexed string types are `Send` and `Sync`, [`SharedIndexedChars`] as long as its string pointer is,
+//! so an index can be built once and shared between threads, such as behind an `Arc`.
+//!
 
 extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
 mod indexed_chars;
 use indexed_chars::IndexedCharsInner;
 
 mod borrowed;
+mod builder;
+mod cursor;
+mod error;
+mod index;
+mod inline;
+mod iter;
+mod offset;
 mod owned;
+mod shared;
+mod writer;
+
+#[cfg(feature = "unicode-segmentation")]
+mod grapheme;
+#[cfg(feature = "packed")]
+mod packed;
+#[cfg(feature = "serde")]
+mod serde_impl;
+
+#[cfg(feature = "arbitrary")]
+mod arbitrary_impl;
 
 pub use borrowed::IndexedChars;
-pub use owned::OwnedIndexedChars;
+pub use builder::IndexBuilder;
+pub use cursor::CharCursor;
+pub use error::CharIndexError;
+#[cfg(feature = "unicode-segmentation")]
+pub use grapheme::GraphemeIndexedChars;
+pub use index::CharIndex;
+pub use inline::InlineIndexedChars;
+pub use iter::{CharChunks, CharIndices, CharWindows, Chars};
+pub use offset::OffsetInt;
+pub use owned::{IndexReuse, OwnedIndexedChars};
+#[cfg(feature = "packed")]
+pub use packed::PackedIndexedChars;
+pub use shared::SharedIndexedChars;
+pub use writer::IndexedWriter;
+
+/// An [`IndexedChars`] that stores its offsets as `u16`, only rolling over every 65535 bytes of non ascii chars.
+pub type IndexedChars16<'a> = IndexedChars<'a, u16>;
+
+/// An [`IndexedChars`] that stores its offsets as `u32`, which practically never rolls over.
+#[cfg(not(target_pointer_width = "16"))]
+pub type IndexedChars32<'a> = IndexedChars<'a, u32>;
+
+/// An [`OwnedIndexedChars`] that stores its offsets as `u16`, only rolling over every 65535 bytes of non ascii chars.
+pub type OwnedIndexedChars16 = OwnedIndexedChars<u16>;
+
+/// An [`OwnedIndexedChars`] that stores its offsets as `u32`, which practically never rolls over.
+#[cfg(not(target_pointer_width = "16"))]
+pub type OwnedIndexedChars32 = OwnedIndexedChars<u32>;
+
+// every type is Send and Sync as long as its backing string is, this fails to compile if a field ever breaks that
+const _: fn() = || {
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    assert_send_sync::<IndexedChars<'static>>();
+    assert_send_sync::<IndexedChars16<'static>>();
+    assert_send_sync::<OwnedIndexedChars>();
+    assert_send_sync::<OwnedIndexedChars16>();
+    assert_send_sync::<SharedIndexedChars<alloc::sync::Arc<str>>>();
+    assert_send_sync::<InlineIndexedChars<'static, 16>>();
+    assert_send_sync::<IndexReuse>();
+    assert_send_sync::<IndexedWriter>();
+    assert_send_sync::<IndexBuilder>();
+    assert_send_sync::<CharCursor<'static>>();
+    assert_send_sync::<Chars<'static>>();
+    assert_send_sync::<CharIndices<'static>>();
+    assert_send_sync::<CharWindows<'static>>();
+    assert_send_sync::<CharChunks<'static>>();
+
+    #[cfg(feature = "unicode-segmentation")]
+    assert_send_sync::<GraphemeIndexedChars<'static>>();
+    #[cfg(feature = "packed")]
+    assert_send_sync::<PackedIndexedChars<'static>>();
+};
@@ -0,0 +1,78 @@
+//! Module containing [`OffsetInt`], the integer types an index can store its char offsets in.
+
+use core::fmt;
+
+/// Private module housing the [`Sealed`](private::Sealed) supertrait of [`OffsetInt`],
+/// this prevents implementations outside this crate, and hides the conversion methods.
+mod private {
+    /// Supertrait of [`OffsetInt`](super::OffsetInt), contains all of the functionality used by the index.
+    pub trait Sealed {
+        /// The largest offset that can be stored before a rollover must occur.
+        const MAX: usize;
+
+        /// Converts a stored offset into a usize.
+        fn to_usize(self) -> usize;
+
+        /// Converts a usize into a stored offset, the value must not be greater than `MAX`.
+        fn from_usize(n: usize) -> Self;
+    }
+}
+
+/// An unsigned integer type that an index can store its char offsets in.
+///
+/// Each char in a non ascii string takes up one offset, so wider types use more memory per char,
+/// but can hold many more bytes of non ascii chars before a rollover occurs (65535 for `u16` instead of 255 for `u8`),
+/// which keeps lookups closer to O(1) for strings made up of mostly non ascii chars.
+/// See the section [`How it Works`](index.html#how-it-works) for details.
+///
+/// This trait is sealed, and is implemented for `u8`, `u16`, and `u32`.
+pub trait OffsetInt: Copy + Eq + fmt::Debug + Send + Sync + 'static + private::Sealed {}
+
+impl private::Sealed for u8 {
+    const MAX: usize = u8::MAX as usize;
+
+    fn to_usize(self) -> usize {
+        usize::from(self)
+    }
+
+    fn from_usize(n: usize) -> Self {
+        // unwrap safe as callers never pass values above MAX
+        n.try_into().unwrap()
+    }
+}
+
+impl OffsetInt for u8 {}
+
+impl private::Sealed for u16 {
+    const MAX: usize = u16::MAX as usize;
+
+    fn to_usize(self) -> usize {
+        usize::from(self)
+    }
+
+    fn from_usize(n: usize) -> Self {
+        // unwrap safe as callers never pass values above MAX
+        n.try_into().unwrap()
+    }
+}
+
+impl OffsetInt for u16 {}
+
+// u32 does not fit in a 16 bit usize
+#[cfg(not(target_pointer_width = "16"))]
+impl private::Sealed for u32 {
+    const MAX: usize = u32::MAX as usize;
+
+    fn to_usize(self) -> usize {
+        // unwrap safe as usize is at least 32 bits here
+        self.try_into().unwrap()
+    }
+
+    fn from_usize(n: usize) -> Self {
+        // unwrap safe as callers never pass values above MAX
+        n.try_into().unwrap()
+    }
+}
+
+#[cfg(not(target_pointer_width = "16"))]
+impl OffsetInt for u32 {}
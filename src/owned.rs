@@ -1,74 +1,1183 @@
 //! Module containing [`OwnedIndexedChars`] and its trait implementations
 
-use alloc::string::String;
+use alloc::{
+    borrow::Cow,
+    string::{FromUtf8Error, String},
+    vec::Vec,
+};
 use core::{
     borrow::Borrow,
     cmp::{Eq, Ord, Ordering, PartialEq, PartialOrd},
+    convert::Infallible,
     fmt,
     hash::{Hash, Hasher},
-    ops::Deref,
+    iter::Rev,
+    ops::{Deref, Range},
+    str::FromStr,
+};
+
+use crate::{
+    offset::OffsetInt, CharChunks, CharCursor, CharIndex, CharIndexError, CharIndices, CharWindows,
+    Chars, IndexedChars, IndexedCharsInner,
 };
 
-use crate::IndexedCharsInner;
+/// A string whose char indices have been cached for ~O(1) char lookup. Owned variant.
+///
+/// This structure allocates 1 additional bytes per unicode scalar value,
+/// which in the case of ascii will only use 2 total bytes for a
+/// single char (as opposed to the 4 bytes required in `Vec<char>`).
+///
+/// As the number of non ascii characters increases, the data density will worsen, until the potential worst case of 5 bytes per character.
+///
+/// The internal representation of this type allows for up to 255 bytes of non ascii unicode chars before an internal rollover occurs (thus tending the complexity towards O(log n)), this is the tradeoff made to reduce memory usage. See the section [`How it Works`](index.html#how-it-works) for details on why char indexing worst case is O(log n), and why in practical cases it appears to be O(1).
+///
+/// For strings made up of mostly non ascii chars, the offsets can be stored in a wider [`OffsetInt`] type to make rollovers much rarer,
+/// at the cost of more memory per char, see [`OwnedIndexedChars16`](crate::OwnedIndexedChars16) and [`OwnedIndexedChars32`](crate::OwnedIndexedChars32).
+///
+/// This type mimics a `String` with its trait impls, including `Clone`, `Default`, `Debug`, `Display`, `FromStr`, `From<Cow<str>>`, `FromIterator<char>`, `FromIterator<&str>`, `Extend<char>`, `Extend<&str>`, `PartialEq` and `PartialOrd` with `String` and `&str` and [`IndexedChars`], `Hash`, `AsRef<str>`/`AsRef<[u8]>`, and `Borrow`.
+///
+/// Like [`IndexedChars`], equality with another indexed string compares cached char counts before any bytes.
+///
+/// Lookups go through [`as_indexed`][OwnedIndexedChars::as_indexed], and are documented with examples on [`IndexedChars`].
+#[derive(Clone)]
+pub struct OwnedIndexedChars<O = u8> {
+    /// Backing string allocation
+    buf: String,
+    /// Char offsets index
+    inner: IndexedCharsInner<O>,
+}
+
+impl OwnedIndexedChars {
+    /// Constructs a new [`OwnedIndexedChars`] instance from a [`String`]. This is O(n), but the cost should only be paid once ideally.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let index = OwnedIndexedChars::new(String::from("foo"));
+    ///
+    /// // we can still access str methods through deref
+    /// _ = index.trim();
+    /// # assert_eq!(index.get_char(0), Some('f'));
+    ///
+    /// // or parse one from a str
+    /// let parsed: OwnedIndexedChars = "foo".parse().unwrap();
+    /// # assert_eq!(parsed, index);
+    /// ```
+    #[must_use]
+    pub fn new(s: String) -> Self {
+        Self::new_with_width(s)
+    }
+
+    /// Constructs a new [`OwnedIndexedChars`] instance with a UTF-16 index, see [`IndexedChars::with_utf16_index`].
+    ///
+    /// The UTF-16 index is kept up to date as the string is modified.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let mut index = OwnedIndexedChars::with_utf16_index(String::from("f💯o"));
+    /// index.insert_char(1, '💯');
+    ///
+    /// assert_eq!(index.char_to_utf16(3), Some(5));
+    /// assert_eq!(index.utf16_to_char(5), Some(3));
+    /// ```
+    #[must_use]
+    pub fn with_utf16_index(s: String) -> Self {
+        let mut inner = IndexedCharsInner::new(&s);
+        inner.index_utf16(&s);
+
+        Self { buf: s, inner }
+    }
+
+    /// Constructs a new [`OwnedIndexedChars`] instance from a vector of bytes, if it is valid UTF-8.
+    ///
+    /// The bytes are reused as the backing `String`, without copying them.
+    ///
+    /// # Errors
+    /// Returns the error from [`String::from_utf8`] if the bytes are not valid UTF-8, which also gives the bytes back.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let index = OwnedIndexedChars::from_utf8("föo".as_bytes().to_vec()).unwrap();
+    ///
+    /// assert_eq!(index.get_char(1), Some('ö'));
+    /// assert!(OwnedIndexedChars::from_utf8(vec![0x66, 0xFF]).is_err());
+    /// ```
+    pub fn from_utf8(bytes: Vec<u8>) -> Result<Self, FromUtf8Error> {
+        String::from_utf8(bytes).map(Self::new)
+    }
+
+    /// Constructs a new [`OwnedIndexedChars`] instance from a byte slice, replacing any invalid UTF-8 sequences with
+    /// [`U+FFFD REPLACEMENT CHARACTER`](char::REPLACEMENT_CHARACTER), like [`String::from_utf8_lossy`].
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let index = OwnedIndexedChars::from_utf8_lossy(&[0x66, 0xFF, 0x6F]);
+    ///
+    /// assert_eq!(index, "f\u{FFFD}o");
+    /// assert_eq!(index.get_char(2), Some('o'));
+    /// ```
+    #[must_use]
+    pub fn from_utf8_lossy(bytes: &[u8]) -> Self {
+        Self::from(String::from_utf8_lossy(bytes))
+    }
+
+    /// Constructs a new [`OwnedIndexedChars`] instance like [`new`][OwnedIndexedChars::new], but splits the indexing work across the rayon thread pool.
+    ///
+    /// This is only worth it for very large strings, smaller ones are indexed on the current thread.
+    /// The resulting index is identical to the one [`new`][OwnedIndexedChars::new] would build.
+    /// Only available with the `rayon` feature enabled.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let s = "föobär".repeat(100_000);
+    /// let index = OwnedIndexedChars::new_parallel(s);
+    ///
+    /// assert_eq!(index.get_char(300_004), Some('ä'));
+    /// ```
+    #[cfg(feature = "rayon")]
+    #[must_use]
+    pub fn new_parallel(s: String) -> Self {
+        let inner = IndexedCharsInner::new_parallel(&s);
+
+        Self { buf: s, inner }
+    }
+}
+
+impl<O: OffsetInt> OwnedIndexedChars<O> {
+    /// Constructs a new [`OwnedIndexedChars`] instance from a [`String`], storing its offsets in `O`.
+    ///
+    /// This is the same as [`new`][OwnedIndexedChars::new] for any offset width, and is mainly useful through the wider type aliases.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars16;
+    /// let index = OwnedIndexedChars16::new_with_width(String::from("föo"));
+    ///
+    /// assert_eq!(index.get_char(1), Some('ö'));
+    /// ```
+    #[must_use]
+    pub fn new_with_width(s: String) -> Self {
+        let inner = IndexedCharsInner::new(&s);
+
+        Self { buf: s, inner }
+    }
+
+    /// Constructs a new [`OwnedIndexedChars`] instance from a [`String`] whose char count is already known,
+    /// skipping the counting pass [`new`][OwnedIndexedChars::new] makes, see [`IndexedChars::new_with_char_count`].
+    #[must_use]
+    pub fn new_with_char_count(s: String, char_count: usize) -> Self {
+        let inner = IndexedCharsInner::new_with_char_count(&s, char_count);
+
+        Self { buf: s, inner }
+    }
+
+    /// Constructs a new [`OwnedIndexedChars`] instance like [`new`][OwnedIndexedChars::new],
+    /// but stores the index in the allocations held by `scratch` instead of allocating new ones.
+    ///
+    /// This is useful when repeatedly rebuilding indexes for similarly sized strings,
+    /// the allocations can be retrieved again with [`into_reuse`][OwnedIndexedChars::into_reuse].
+    /// Note that any spare capacity from `scratch` is kept by the index, and counted by [`memory_footprint`][OwnedIndexedChars::memory_footprint].
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::{IndexReuse, OwnedIndexedChars};
+    /// let mut scratch = IndexReuse::new();
+    ///
+    /// for s in ["föo", "bär", "bäz"] {
+    ///     let index = OwnedIndexedChars::from_string_reusing(String::from(s), scratch);
+    ///
+    ///     assert_eq!(index.get_char(1), s.chars().nth(1));
+    ///
+    ///     scratch = index.into_reuse().1;
+    /// }
+    /// ```
+    #[must_use]
+    pub fn from_string_reusing(buf: String, scratch: IndexReuse<O>) -> Self {
+        let inner = IndexedCharsInner::new_in(&buf, scratch.chars, scratch.rollovers);
+
+        Self { buf, inner }
+    }
+
+    /// Drops index data and returns backing `String` allocation, alongside the allocations used by the index,
+    /// so they can be reused by [`from_string_reusing`][OwnedIndexedChars::from_string_reusing].
+    #[must_use]
+    pub fn into_reuse(self) -> (String, IndexReuse<O>) {
+        let (chars, rollovers) = self.inner.into_vecs();
+
+        (self.buf, IndexReuse { chars, rollovers })
+    }
+
+    /// Takes apart this [`OwnedIndexedChars`], returning the backing string, the per char offsets, and the rollover points of the index.
+    ///
+    /// This allows caching the index separately from the string, and reattaching them later with `from_parts` (available with the `unsafe` feature) without recomputing it.
+    /// The layout of the index is described in [`How it Works`](index.html#how-it-works), the offsets are empty if the string is ascii.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let (buf, chars, rollovers) = OwnedIndexedChars::new(String::from("föo")).into_parts();
+    ///
+    /// assert_eq!(buf, "föo");
+    /// assert_eq!(chars, [0, 0, 1]);
+    /// assert!(rollovers.is_empty());
+    /// ```
+    #[must_use]
+    pub fn into_parts(self) -> (String, Vec<O>, Vec<usize>) {
+        let (chars, rollovers) = self.inner.into_vecs();
+
+        (self.buf, chars, rollovers)
+    }
+
+    /// Reassembles an [`OwnedIndexedChars`] from the parts returned by [`into_parts`][OwnedIndexedChars::into_parts], without recomputing the index.
+    ///
+    /// Only available with the `unsafe` feature enabled.
+    ///
+    /// # Safety
+    /// `chars` and `rollovers` must be exactly the index that [`new`][OwnedIndexedChars::new] would build for `buf`,
+    /// such as the parts returned by [`into_parts`][OwnedIndexedChars::into_parts] with the string unmodified.
+    /// A mismatched index makes unchecked lookups such as [`get_char_unchecked`][OwnedIndexedChars::get_char_unchecked] undefined behavior,
+    /// and makes other methods panic or return incorrect results. [`validate`][OwnedIndexedChars::validate] can be used to check the parts while testing.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let (buf, chars, rollovers) = OwnedIndexedChars::new(String::from("föo")).into_parts();
+    ///
+    /// // SAFETY: the parts are unmodified from into_parts
+    /// let index = unsafe { OwnedIndexedChars::from_parts(buf, chars, rollovers) };
+    ///
+    /// assert_eq!(index.get_char(2), Some('o'));
+    /// ```
+    #[cfg(feature = "unsafe")]
+    #[allow(unsafe_code)]
+    #[must_use]
+    pub unsafe fn from_parts(buf: String, chars: Vec<O>, rollovers: Vec<usize>) -> Self {
+        // the parts are trusted to match buf, so the char count can be taken from them
+        let char_len = if chars.is_empty() {
+            buf.len()
+        } else {
+            chars.len()
+        };
+        let inner = IndexedCharsInner::from_vecs(chars, rollovers, char_len);
+
+        Self { buf, inner }
+    }
+
+    /// Checks that the index matches the backing string, by rebuilding it and comparing it to the stored one.
+    ///
+    /// This is always true for an index built by this crate, and is meant for checking parts passed to `from_parts`
+    /// in tests and while debugging. It is O(n) and allocates, so it should be kept out of hot paths.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let index = OwnedIndexedChars::new(String::from("föo"));
+    ///
+    /// assert!(index.validate());
+    /// ```
+    #[must_use]
+    pub fn validate(&self) -> bool {
+        self.inner.validate(&self.buf)
+    }
+
+    /// Creates an [`OwnedIndexedChars`] from an already built index, the index passed must be the one built for `buf`.
+    pub(crate) fn from_inner(buf: String, inner: IndexedCharsInner<O>) -> Self {
+        Self { buf, inner }
+    }
+
+    /// Indexes into the backing string to retrieve the nth codepoint.
+    ///
+    /// See [`IndexedChars::get_char`].
+    #[must_use]
+    pub fn get_char(&self, index: usize) -> Option<char> {
+        self.as_indexed().get_char(index)
+    }
+
+    /// Indexes into the backing string to retrieve the nth codepoint, or returns `default` if the index is out of bounds.
+    ///
+    /// See [`IndexedChars::get_char_or`].
+    #[must_use]
+    pub fn get_char_or(&self, index: usize, default: char) -> char {
+        self.as_indexed().get_char_or(index, default)
+    }
+
+    /// Indexes into the backing string to retrieve the nth char, alongside the byte range it spans in the backing string.
+    ///
+    /// See [`IndexedChars::char_and_span`].
+    #[must_use]
+    pub fn char_and_span(&self, index: usize) -> Option<(char, Range<usize>)> {
+        self.as_indexed().char_and_span(index)
+    }
+
+    /// Returns `true` if the char at `index` is `c`, and `false` if it is not or the index is out of bounds.
+    ///
+    /// See [`IndexedChars::nth_char_is`].
+    #[must_use]
+    pub fn nth_char_is(&self, index: usize, c: char) -> bool {
+        self.as_indexed().nth_char_is(index, c)
+    }
+
+    /// Retrieves the chars at several char indices at once, as if by calling [`get_char`][OwnedIndexedChars::get_char] for each of them.
+    ///
+    /// See [`IndexedChars::get_many`].
+    #[must_use]
+    pub fn get_many<const N: usize>(&self, indices: [usize; N]) -> [Option<char>; N] {
+        self.as_indexed().get_many(indices)
+    }
+
+    /// Returns a [`CharCursor`] at the start of the backing string, for looking up chars near each other faster than [`get_char`][OwnedIndexedChars::get_char].
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let index = OwnedIndexedChars::new(String::from("föo"));
+    /// let mut cursor = index.cursor();
+    ///
+    /// let chars: Vec<_> = (0..index.len_chars()).map(|i| cursor.advance_to(i).unwrap()).collect();
+    ///
+    /// assert_eq!(chars, ['f', 'ö', 'o']);
+    /// ```
+    #[must_use]
+    pub fn cursor(&self) -> CharCursor<'_, O> {
+        CharCursor::new(&self.buf, &self.inner)
+    }
+
+    /// Indexes into the backing string to retrieve the nth codepoint, panicking if it is out of bounds.
+    ///
+    /// See [`IndexedChars::char_at`].
+    ///
+    /// # Panics
+    /// Panics if `index` is not less than [`char_count`][OwnedIndexedChars::char_count].
+    #[must_use]
+    #[track_caller]
+    pub fn char_at(&self, index: usize) -> char {
+        self.as_indexed().char_at(index)
+    }
+
+    /// Returns the first char of the backing string, or `None` if it is empty.
+    ///
+    /// See [`IndexedChars::first_char`].
+    #[must_use]
+    pub fn first_char(&self) -> Option<char> {
+        self.as_indexed().first_char()
+    }
+
+    /// Returns the last char of the backing string in O(1), or `None` if it is empty.
+    ///
+    /// See [`IndexedChars::last_char`].
+    #[must_use]
+    pub fn last_char(&self) -> Option<char> {
+        self.as_indexed().last_char()
+    }
+
+    /// Indexes into the backing string to retrieve the nth codepoint, without checking that the index is in bounds.
+    ///
+    /// This is the same as [`get_char`][OwnedIndexedChars::get_char] but skips its bounds checks, for hot loops where the index is already known to be valid.
+    /// Only available with the `unsafe` feature enabled.
+    ///
+    /// # Safety
+    /// `index` must be less than [`char_count`][OwnedIndexedChars::char_count], calling this with an out of bounds index is undefined behavior.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let s = OwnedIndexedChars::new(String::from("föo"));
+    ///
+    /// for i in 0..s.char_count() {
+    ///     // SAFETY: i is below the char count
+    ///     let c = unsafe { s.get_char_unchecked(i) };
+    ///     # assert_eq!(Some(c), s.get_char(i));
+    /// }
+    /// ```
+    #[cfg(feature = "unsafe")]
+    #[allow(unsafe_code)]
+    #[must_use]
+    pub unsafe fn get_char_unchecked(&self, index: usize) -> char {
+        // SAFETY: caller guarantees index is in bounds, and our index always matches our backing string
+        self.inner.get_char_unchecked(&self.buf, index)
+    }
+
+    /// Converts a char index into the byte index where that char starts in the backing string.
+    ///
+    /// See [`IndexedChars::char_to_byte`].
+    #[must_use]
+    pub fn char_to_byte(&self, index: usize) -> Option<usize> {
+        self.as_indexed().char_to_byte(index)
+    }
+
+    /// Returns an iterator over the byte index where each char starts in the backing string, in char order.
+    ///
+    /// This yields what [`char_to_byte`][OwnedIndexedChars::char_to_byte] returns for every index,
+    /// but walks the index once instead of searching it for each char.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let index = OwnedIndexedChars::new(String::from("föo!"));
+    ///
+    /// assert!(index.char_offsets().eq([0, 1, 3, 4]));
+    /// ```
+    pub fn char_offsets(&self) -> impl Iterator<Item = usize> + '_ {
+        self.inner.char_offsets()
+    }
+
+    /// Returns the byte index of the boundary before the nth char, suitable for passing to [`str::split_at`].
+    ///
+    /// See [`IndexedChars::nth_char_boundary`].
+    #[must_use]
+    pub fn nth_char_boundary(&self, char_index: usize) -> Option<usize> {
+        self.as_indexed().nth_char_boundary(char_index)
+    }
+
+    /// Divides the backing string into two substrings at a char index, like [`str::split_at`] but indexed by chars.
+    ///
+    /// See [`IndexedChars::split_at_char`].
+    #[must_use]
+    pub fn split_at_char(&self, char_index: usize) -> Option<(&str, &str)> {
+        self.as_indexed().split_at_char(char_index)
+    }
+
+    /// Checks that the backing string is equal to `other`, ignoring ascii case, without allocating.
+    ///
+    /// See [`IndexedChars::eq_ignore_ascii_case`].
+    #[must_use]
+    pub fn eq_ignore_ascii_case(&self, other: &str) -> bool {
+        self.as_indexed().eq_ignore_ascii_case(other)
+    }
+
+    /// Returns `true` if the chars of the backing string are exactly the chars yielded by `iter`, without collecting them into a string.
+    ///
+    /// See [`IndexedChars::eq_chars`].
+    #[must_use]
+    pub fn eq_chars<I: IntoIterator<Item = char>>(&self, iter: I) -> bool {
+        self.as_indexed().eq_chars(iter)
+    }
+
+    /// Returns the backing string in Unicode Normalization Form C, borrowing it if it is already normalized.
+    ///
+    /// See [`IndexedChars::normalized`].
+    #[cfg(feature = "unicode-normalization")]
+    #[must_use]
+    pub fn normalized(&self) -> Cow<'_, str> {
+        self.as_indexed().normalized()
+    }
+
+    /// Converts a byte index into the index of the char that starts at that byte in the backing string.
+    ///
+    /// See [`IndexedChars::byte_to_char`].
+    #[must_use]
+    pub fn byte_to_char(&self, byte_index: usize) -> Option<usize> {
+        self.as_indexed().byte_to_char(byte_index)
+    }
+
+    /// Returns the number of chars in the substring spanning the bytes in `range`, without scanning it.
+    ///
+    /// See [`IndexedChars::char_count_in_range`].
+    #[must_use]
+    pub fn char_count_in_range(&self, range: Range<usize>) -> Option<usize> {
+        self.as_indexed().char_count_in_range(range)
+    }
+
+    /// Converts a char index into its zero based `(line, column)` position, where lines are separated by `\n` and columns count chars.
+    ///
+    /// See [`IndexedChars::line_col`].
+    #[must_use]
+    pub fn line_col(&self, char_index: usize) -> Option<(usize, usize)> {
+        self.as_indexed().line_col(char_index)
+    }
+
+    /// Returns the width of the backing string when displayed in a terminal, in columns, as the sum of the widths of its chars.
+    ///
+    /// See [`IndexedChars::display_width`].
+    #[cfg(feature = "unicode-width")]
+    #[must_use]
+    pub fn display_width(&self) -> usize {
+        self.as_indexed().display_width()
+    }
+
+    /// Converts a display column into the index of the char covering it, such as for placing a cursor at a terminal column.
+    ///
+    /// See [`IndexedChars::width_to_char`].
+    #[cfg(feature = "unicode-width")]
+    #[must_use]
+    pub fn width_to_char(&self, column: usize) -> Option<usize> {
+        self.as_indexed().width_to_char(column)
+    }
+
+    /// Converts a char index into the index of its first UTF-16 code unit, for interop with UTF-16 based APIs.
+    ///
+    /// See [`IndexedChars::char_to_utf16`].
+    #[must_use]
+    pub fn char_to_utf16(&self, index: usize) -> Option<usize> {
+        self.as_indexed().char_to_utf16(index)
+    }
+
+    /// Converts a UTF-16 code unit index into a char index, for interop with UTF-16 based APIs.
+    ///
+    /// See [`IndexedChars::utf16_to_char`].
+    #[must_use]
+    pub fn utf16_to_char(&self, utf16_index: usize) -> Option<usize> {
+        self.as_indexed().utf16_to_char(utf16_index)
+    }
+
+    /// Returns the char index of the first occurrence of `c` in the backing string, or `None` if it does not occur.
+    ///
+    /// See [`IndexedChars::find_char`].
+    #[must_use]
+    pub fn find_char(&self, c: char) -> Option<usize> {
+        self.as_indexed().find_char(c)
+    }
+
+    /// Returns the char index of the last occurrence of `c` in the backing string, or `None` if it does not occur.
+    ///
+    /// See [`IndexedChars::rfind_char`].
+    #[must_use]
+    pub fn rfind_char(&self, c: char) -> Option<usize> {
+        self.as_indexed().rfind_char(c)
+    }
+
+    /// Returns the char index where `pat` first occurs in the backing string, or `None` if it does not occur.
+    ///
+    /// See [`IndexedChars::find_str`].
+    #[must_use]
+    pub fn find_str(&self, pat: &str) -> Option<usize> {
+        self.as_indexed().find_str(pat)
+    }
+
+    /// Returns the number of occurrences of `c` in the backing string.
+    ///
+    /// See [`IndexedChars::count_matches`].
+    #[must_use]
+    pub fn count_matches(&self, c: char) -> usize {
+        self.as_indexed().count_matches(c)
+    }
+
+    /// Returns `true` if `c` occurs in the backing string.
+    ///
+    /// See [`IndexedChars::contains_char`].
+    #[must_use]
+    pub fn contains_char(&self, c: char) -> bool {
+        self.as_indexed().contains_char(c)
+    }
+
+    /// Gets the substring of the backing string spanning the chars in `range`.
+    ///
+    /// See [`IndexedChars::get_str`].
+    #[must_use]
+    pub fn get_str(&self, range: Range<usize>) -> Option<&str> {
+        self.as_indexed().get_str(range)
+    }
+
+    /// Converts a range of chars into the range of bytes spanning them in the backing string, for slicing its bytes by char bounds.
+    ///
+    /// See [`IndexedChars::char_to_byte_range`].
+    #[must_use]
+    pub fn char_to_byte_range(&self, char_range: Range<usize>) -> Option<Range<usize>> {
+        self.as_indexed().char_to_byte_range(char_range)
+    }
+
+    /// Same as [`get_str`][OwnedIndexedChars::get_str], but returns why the range could not be looked up instead of `None`.
+    ///
+    /// See [`IndexedChars::try_get_str`].
+    ///
+    /// # Errors
+    /// Returns [`CharIndexError::InvalidRange`] if the range is reversed, and [`CharIndexError::OutOfBounds`] if it reaches past the last char.
+    pub fn try_get_str(&self, range: Range<usize>) -> Result<&str, CharIndexError> {
+        self.as_indexed().try_get_str(range)
+    }
+
+    /// Same as [`char_to_byte_range`][OwnedIndexedChars::char_to_byte_range], but returns why the range could not be looked up instead of `None`.
+    ///
+    /// See [`IndexedChars::try_char_to_byte_range`].
+    ///
+    /// # Errors
+    /// Returns [`CharIndexError::InvalidRange`] if the range is reversed, and [`CharIndexError::OutOfBounds`] if it reaches past the last char.
+    pub fn try_char_to_byte_range(
+        &self,
+        char_range: Range<usize>,
+    ) -> Result<Range<usize>, CharIndexError> {
+        self.as_indexed().try_char_to_byte_range(char_range)
+    }
+
+    /// Returns the length of the backing string in bytes, this is the same as [`str::len`].
+    ///
+    /// See [`IndexedChars::byte_len`].
+    #[must_use]
+    pub fn byte_len(&self) -> usize {
+        self.as_indexed().byte_len()
+    }
+
+    /// Looks up a char or a substring by char index, depending on the type of `index`, like [`slice::get`].
+    ///
+    /// A `usize` retrieves the char at that index like [`get_char`][OwnedIndexedChars::get_char],
+    /// and a range retrieves the substring spanning those chars like [`get_str`][OwnedIndexedChars::get_str], see [`CharIndex`].
+    /// Note that `str::get`, reachable through `Deref`, takes a range of bytes instead.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let index = OwnedIndexedChars::new(String::from("föo"));
+    ///
+    /// assert_eq!(index.get_at(1), Some('ö'));
+    /// assert_eq!(index.get_at(1..3), Some("öo"));
+    /// assert_eq!(index.get_at(..=1), Some("fö"));
+    /// assert_eq!(index.get_at(4..), None);
+    /// ```
+    #[must_use]
+    pub fn get_at<'s, I: CharIndex<'s>>(&'s self, index: I) -> Option<I::Output> {
+        index.get_in(&self.as_indexed())
+    }
+
+    /// Returns a value that displays the chars in `range` without allocating, for logging part of a large string.
+    ///
+    /// See [`IndexedChars::display_range`].
+    #[must_use]
+    pub fn display_range(&self, range: Range<usize>) -> impl fmt::Display + '_ {
+        self.as_indexed().display_range(range)
+    }
+
+    /// Returns a view of this string with leading and trailing whitespace removed, as with [`str::trim`], keeping it indexed.
+    ///
+    /// The view is created as with [`subindex`][IndexedChars::subindex], so no rescan of the string is needed.
+    /// This is not named `trim` so as to not shadow [`str::trim`], which stays available through `Deref`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let index = OwnedIndexedChars::new(String::from(" föo bär\n"));
+    /// let trimmed = index.trim_indexed();
+    ///
+    /// assert_eq!(trimmed, "föo bär");
+    /// assert_eq!(trimmed.get_char(5), Some('ä'));
+    /// ```
+    #[must_use]
+    pub fn trim_indexed(&self) -> IndexedChars<'_, O> {
+        self.as_indexed().trim_indexed()
+    }
+
+    /// Same as [`trim_indexed`][OwnedIndexedChars::trim_indexed], but only removes leading whitespace, as with [`str::trim_start`].
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let index = OwnedIndexedChars::new(String::from(" föo bär\n"));
+    /// let trimmed = index.trim_start_indexed();
+    ///
+    /// assert_eq!(trimmed, "föo bär\n");
+    /// assert_eq!(trimmed.get_char(1), Some('ö'));
+    /// ```
+    #[must_use]
+    pub fn trim_start_indexed(&self) -> IndexedChars<'_, O> {
+        self.as_indexed().trim_start_indexed()
+    }
+
+    /// Same as [`trim_indexed`][OwnedIndexedChars::trim_indexed], but only removes trailing whitespace, as with [`str::trim_end`].
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let index = OwnedIndexedChars::new(String::from(" föo bär\n"));
+    /// let trimmed = index.trim_end_indexed();
+    ///
+    /// assert_eq!(trimmed, " föo bär");
+    /// assert_eq!(trimmed.get_char(6), Some('ä'));
+    /// ```
+    #[must_use]
+    pub fn trim_end_indexed(&self) -> IndexedChars<'_, O> {
+        self.as_indexed().trim_end_indexed()
+    }
+
+    /// Gets the substring of the backing string spanning the chars in `range`, like [`get_str`][OwnedIndexedChars::get_str] but panicking instead of returning `None`.
+    ///
+    /// See [`IndexedChars::index_chars`].
+    ///
+    /// # Panics
+    /// Panics if the range is reversed or its end is greater than [`len_chars`][OwnedIndexedChars::len_chars].
+    #[must_use]
+    #[track_caller]
+    pub fn index_chars(&self, range: Range<usize>) -> &str {
+        self.as_indexed().index_chars(range)
+    }
+
+    /// Borrows this instance as an [`IndexedChars`](crate::IndexedChars), this is O(1) as the index is shared rather than rebuilt.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::{IndexedChars, OwnedIndexedChars};
+    /// fn third(index: &IndexedChars) -> Option<char> {
+    ///     index.get_char(2)
+    /// }
+    ///
+    /// let index = OwnedIndexedChars::new(String::from("föo"));
+    ///
+    /// assert_eq!(third(&index.as_indexed()), Some('o'));
+    /// ```
+    #[must_use]
+    pub fn as_indexed(&self) -> IndexedChars<'_, O> {
+        IndexedChars::from_borrowed(&self.buf, &self.inner)
+    }
+
+    /// Returns the number of chars present in the backing string, this operation is free thanks to
+    /// how [`OwnedIndexedChars`] is constructed
+    ///
+    /// See [`IndexedChars::char_count`].
+    #[must_use]
+    pub fn char_count(&self) -> usize {
+        self.as_indexed().char_count()
+    }
+
+    /// Returns the number of chars (unicode scalar values) present in the backing string in O(1).
+    ///
+    /// See [`IndexedChars::len_chars`].
+    #[must_use]
+    pub fn len_chars(&self) -> usize {
+        self.as_indexed().len_chars()
+    }
+
+    /// Returns `true` if the backing string is empty.
+    ///
+    /// See [`IndexedChars::is_empty`].
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.as_indexed().is_empty()
+    }
+
+    /// Returns `true` if the ascii optimization is active, which is the case exactly when the backing string is pure ascii.
+    ///
+    /// See [`IndexedChars::is_ascii`].
+    #[must_use]
+    pub fn is_ascii(&self) -> bool {
+        self.as_indexed().is_ascii()
+    }
+
+    /// Returns the amount of rollovers that occurred while building the index.
+    ///
+    /// See [`IndexedChars::rollover_count`].
+    #[must_use]
+    pub fn rollover_count(&self) -> usize {
+        self.as_indexed().rollover_count()
+    }
+
+    /// Returns the amount of heap memory in bytes used by the index, not counting the backing string.
+    ///
+    /// See [`IndexedChars::memory_footprint`].
+    #[must_use]
+    pub fn memory_footprint(&self) -> usize {
+        self.as_indexed().memory_footprint()
+    }
+
+    /// Returns the amount of char offsets the index can hold without reallocating.
+    ///
+    /// This is 0 for ascii strings, which store no offsets.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let mut index = OwnedIndexedChars::new(String::from("föo"));
+    ///
+    /// index.reserve(100);
+    /// assert!(index.capacity_chars() >= 103);
+    ///
+    /// index.shrink_to_fit();
+    /// assert_eq!(index.capacity_chars(), 3);
+    /// assert_eq!(index.capacity_rollovers(), 0);
+    /// ```
+    #[must_use]
+    pub fn capacity_chars(&self) -> usize {
+        self.inner.capacity_chars()
+    }
+
+    /// Returns the amount of rollover points the index can hold without reallocating.
+    #[must_use]
+    pub fn capacity_rollovers(&self) -> usize {
+        self.inner.capacity_rollovers()
+    }
 
-/// A string whose char indices have been cached for ~O(1) char lookup. Owned variant.
-///
-/// This structure allocates 1 additional bytes per unicode scalar value,
-/// which in the case of ascii will only use 2 total bytes for a
-/// single char (as opposed to the 4 bytes required in `Vec<char>`).
-///
-/// As the number of non ascii characters increases, the data density will worsen, until the potential worst case of 5 bytes per character.
-///
-/// The internal representation of this type allows for up to 255 bytes of non ascii unicode chars before an internal rollover occurs (thus tending the complexity towards O(log n)), this is the tradeoff made to reduce memory usage. See the section [`How it Works`](index.html#how-it-works) for details on why char indexing worst case is O(log n), and why in practical cases it appears to be O(1).
-///
-/// This type mimics a `String` with its trait impls, including `Debug`, `Display`, `PartialEq` with `&str` `PartialOrd` with `&str`, `Hash`, and `AsRef`/`Borrow`.
-pub struct OwnedIndexedChars {
-    /// Backing string allocation
-    buf: String,
-    /// Char offsets index
-    inner: IndexedCharsInner,
-}
+    /// Returns an iterator over the chars of the backing string.
+    ///
+    /// See [`IndexedChars::chars`].
+    #[must_use]
+    pub fn chars(&self) -> Chars<'_> {
+        self.as_indexed().chars()
+    }
 
-impl OwnedIndexedChars {
-    /// Constructs a new [`OwnedIndexedChars`] instance from a [`String`]. This is O(n), but the cost should only be paid once ideally.
+    /// Clears `out` and fills it with the chars of the backing string, reusing its allocation.
+    ///
+    /// See [`IndexedChars::collect_chars_into`].
+    pub fn collect_chars_into(&self, out: &mut Vec<char>) {
+        self.as_indexed().collect_chars_into(out);
+    }
+
+    /// Returns an iterator over the chars of the backing string, starting at the char at `index`.
+    ///
+    /// See [`IndexedChars::chars_from`].
+    #[must_use]
+    pub fn chars_from(&self, index: usize) -> Option<Chars<'_>> {
+        self.as_indexed().chars_from(index)
+    }
+
+    /// Returns an iterator over the chars of the backing string in reverse order, from last to first.
+    ///
+    /// See [`IndexedChars::rchars`].
+    pub fn rchars(&self) -> Rev<Chars<'_>> {
+        self.as_indexed().rchars()
+    }
+
+    /// Returns a new [`OwnedIndexedChars`] of the chars of the backing string in reverse order.
+    ///
+    /// See [`IndexedChars::reversed`].
+    #[must_use]
+    pub fn reversed(&self) -> OwnedIndexedChars<O> {
+        self.as_indexed().reversed()
+    }
+
+    /// Returns an iterator over the chars of the backing string and their positions.
+    ///
+    /// See [`IndexedChars::char_indices`].
+    #[must_use]
+    pub fn char_indices(&self) -> CharIndices<'_> {
+        self.as_indexed().char_indices()
+    }
+
+    /// Returns an iterator over the chars of the backing string and the byte index each starts at, in order.
+    ///
+    /// See [`IndexedChars::positions`].
+    #[must_use]
+    pub fn positions(
+        &self,
+    ) -> impl ExactSizeIterator<Item = (char, usize)> + DoubleEndedIterator + '_ {
+        self.as_indexed().positions()
+    }
+
+    /// Returns an iterator over the chars of the backing string and their char indices in reverse order, from last to first.
+    ///
+    /// See [`IndexedChars::rchar_indices`].
+    #[must_use]
+    pub fn rchar_indices(
+        &self,
+    ) -> impl ExactSizeIterator<Item = (usize, char)> + DoubleEndedIterator + '_ {
+        self.as_indexed().rchar_indices()
+    }
+
+    /// Returns an iterator over all overlapping windows of `size` chars in the backing string, as string slices.
+    ///
+    /// See [`IndexedChars::char_windows`].
+    ///
+    /// # Panics
+    /// Panics if `size` is 0.
+    #[must_use]
+    pub fn char_windows(&self, size: usize) -> CharWindows<'_> {
+        self.as_indexed().char_windows(size)
+    }
+
+    /// Returns an iterator over non overlapping chunks of `size` chars in the backing string, as string slices.
+    ///
+    /// See [`IndexedChars::char_chunks`].
+    ///
+    /// # Panics
+    /// Panics if `size` is 0.
+    #[must_use]
+    pub fn char_chunks(&self, size: usize) -> CharChunks<'_> {
+        self.as_indexed().char_chunks(size)
+    }
+
+    /// Returns an iterator over the substrings of the backing string separated by `pat`, each paired with the char index it starts at.
+    ///
+    /// See [`IndexedChars::split_char_indices`].
+    pub fn split_char_indices(&self, pat: char) -> impl Iterator<Item = (usize, &'_ str)> + '_ {
+        self.as_indexed().split_char_indices(pat)
+    }
+
+    /// Appends a char to the end of the backing `String`, extending the index in place instead of rebuilding it.
+    ///
+    /// This is amortized O(1), except for the first time a non ascii char is appended,
+    /// as the offsets of the ascii prefix must then be stored.
     ///
     /// # Examples
     /// ```rust
     /// # use char_index::OwnedIndexedChars;
-    /// let index = OwnedIndexedChars::new(String::from("foo"));
+    /// let mut index = OwnedIndexedChars::new(String::from("fo"));
+    /// index.push('ö');
     ///
-    /// // we can still access str methods through deref
-    /// _ = index.chars();
-    /// # assert_eq!(index.get_char(0), Some('f'));
+    /// assert_eq!(index, "foö");
+    /// assert_eq!(index.get_char(2), Some('ö'));
     /// ```
-    #[must_use]
-    pub fn new(s: String) -> Self {
-        let inner = IndexedCharsInner::new(&s);
+    pub fn push(&mut self, c: char) {
+        self.inner.push_char(&self.buf, c);
+        self.buf.push(c);
+    }
 
-        Self { buf: s, inner }
+    /// Appends a string to the end of the backing `String`, extending the index in place instead of rebuilding it.
+    ///
+    /// This is O(n) in the length of `s`, plus the length of the backing string the first time a non ascii char is appended,
+    /// as the offsets of the ascii prefix must then be stored.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let mut index = OwnedIndexedChars::new(String::from("foo"));
+    /// index.push_str("bär");
+    ///
+    /// assert_eq!(index, "foobär");
+    /// assert_eq!(index.get_char(4), Some('ä'));
+    /// assert_eq!(index.get_char(5), Some('r'));
+    /// ```
+    pub fn push_str(&mut self, s: &str) {
+        self.inner.push_str(&self.buf, s);
+        self.buf.push_str(s);
     }
 
-    /// Indexes into the backing string to retrieve the nth codepoint.
+    /// Removes the last char from the backing `String` and returns it, or `None` if it is empty.
     ///
-    /// This operation has an average case of O(1), and a worst case of O(log n).
+    /// The index is shrunk along with the string, dropping the last rollover if the removed char was the one that caused it.
     ///
     /// # Examples
     /// ```rust
     /// # use char_index::OwnedIndexedChars;
-    /// let s = OwnedIndexedChars::new(String::from("foo"));
+    /// // each 'ö' adds 1 to the offset, so the 257th one causes a rollover
+    /// let mut index = OwnedIndexedChars::new("ö".repeat(257));
+    ///
+    /// assert_eq!(index.rollover_count(), 1);
+    /// assert_eq!(index.pop(), Some('ö'));
+    /// assert_eq!(index.rollover_count(), 0);
+    /// assert_eq!(index.len_chars(), 256);
     ///
-    /// assert_eq!(s.get_char(1), Some('o'));
+    /// let mut index = OwnedIndexedChars::new(String::from("fö"));
+    ///
+    /// assert_eq!(index.pop(), Some('ö'));
+    /// assert_eq!(index.pop(), Some('f'));
+    /// assert_eq!(index.pop(), None);
     /// ```
-    #[must_use]
-    pub fn get_char(&self, index: usize) -> Option<char> {
-        self.inner.get_char(&self.buf, index)
+    pub fn pop(&mut self) -> Option<char> {
+        let c = self.buf.chars().next_back()?;
+
+        self.truncate_chars(self.char_count() - 1);
+
+        Some(c)
     }
 
-    /// Returns the number of chars present in the backing string, this operation is free thanks to
-    /// how [`OwnedIndexedChars`] is constructed
+    /// Removes the char at `index` from the backing `String` and returns it, or `None` if the index is out of bounds.
+    ///
+    /// Every char after the removed one shifts back, so the index is recomputed from the removal point onward, which is O(n).
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let mut index = OwnedIndexedChars::new(String::from("föobär"));
+    ///
+    /// assert_eq!(index.remove_char(1), Some('ö'));
+    /// assert_eq!(index, "fobär");
+    /// assert_eq!(index.get_char(3), Some('ä'));
+    /// assert_eq!(index.remove_char(5), None);
+    /// ```
+    pub fn remove_char(&mut self, index: usize) -> Option<char> {
+        let byte_index = self.char_to_byte(index)?;
+
+        let c = self.buf.remove(byte_index);
+        self.inner.reindex_from(&self.buf, index, byte_index);
+
+        Some(c)
+    }
+
+    /// Inserts a char into the backing `String` at the char index `index`, `index == len_chars` appends it.
+    ///
+    /// Every char after the inserted one shifts forward, so the index is recomputed from the insertion point onward, which is O(n).
+    ///
+    /// # Panics
+    /// Panics if `index` is greater than [`len_chars`][OwnedIndexedChars::len_chars].
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let mut index = OwnedIndexedChars::new(String::from("foobr"));
+    ///
+    /// index.insert_char(4, 'ä');
+    /// index.insert_char(1, 'ö');
+    /// index.insert_char(7, '!');
+    ///
+    /// assert_eq!(index, "föoobär!");
+    /// assert_eq!(index.get_char(5), Some('ä'));
+    /// ```
+    #[track_caller]
+    pub fn insert_char(&mut self, index: usize, c: char) {
+        let byte_index = match self.nth_char_boundary(index) {
+            Some(byte_index) => byte_index,
+            None => panic!(
+                "insertion index {} out of bounds (len {})",
+                index,
+                self.char_count()
+            ),
+        };
+
+        self.buf.insert(byte_index, c);
+        self.inner.reindex_from(&self.buf, index, byte_index);
+    }
+
+    /// Shortens the backing `String` to its first `char_len` chars, keeping the index consistent with it.
+    ///
+    /// If `char_len` is greater than or equal to [`len_chars`][OwnedIndexedChars::len_chars], this has no effect.
+    /// This has the same complexity as [`get_char`][OwnedIndexedChars::get_char], plus the cost of the truncation itself.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let mut index = OwnedIndexedChars::new(String::from("föobär"));
+    /// index.truncate_chars(4);
+    ///
+    /// assert_eq!(index, "föob");
+    /// assert_eq!(index.len_chars(), 4);
+    ///
+    /// index.truncate_chars(10);
+    ///
+    /// assert_eq!(index, "föob");
+    /// ```
+    pub fn truncate_chars(&mut self, char_len: usize) {
+        if let Some(byte_len) = self.inner.truncate(&self.buf, char_len) {
+            self.buf.truncate(byte_len);
+        }
+    }
+
+    /// Replaces the chars in `range` with `replacement`, like [`String::replace_range`] but with a range of chars.
+    ///
+    /// Everything after the replaced chars may shift, so the index is recomputed from the start of the range onward, which is O(n).
+    ///
+    /// # Panics
+    /// Panics if the range is reversed or its end is greater than [`len_chars`][OwnedIndexedChars::len_chars].
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let mut index = OwnedIndexedChars::new(String::from("föobär"));
+    /// index.replace_range_chars(1..3, "💯");
+    ///
+    /// assert_eq!(index, "f💯bär");
+    /// assert_eq!(index.get_char(3), Some('ä'));
+    /// ```
+    #[track_caller]
+    pub fn replace_range_chars(&mut self, range: Range<usize>, replacement: &str) {
+        let (start, end) = match (
+            self.nth_char_boundary(range.start),
+            self.nth_char_boundary(range.end),
+        ) {
+            (Some(start), Some(end)) if start <= end => (start, end),
+            _ if range.start > range.end => panic!(
+                "char range starts at {} but ends at {}",
+                range.start, range.end
+            ),
+            _ => panic!(
+                "char range {}..{} out of bounds (len {})",
+                range.start,
+                range.end,
+                self.char_count()
+            ),
+        };
+
+        self.buf.replace_range(start..end, replacement);
+        self.inner.reindex_from(&self.buf, range.start, start);
+    }
+
+    /// Retains only the chars for which `f` returns `true`, like [`String::retain`].
+    ///
+    /// The index is rebuilt in the same pass that filters the backing string, so this is O(n) and visits each char once.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let mut index = OwnedIndexedChars::new(String::from("föobär"));
+    /// index.retain_chars(|c| c.is_ascii() && c != 'o');
+    ///
+    /// assert_eq!(index, "fbr");
+    /// assert_eq!(index.get_char(1), Some('b'));
+    /// assert!(index.is_ascii());
+    /// ```
+    pub fn retain_chars<F: FnMut(char) -> bool>(&mut self, mut f: F) {
+        let inner = &mut self.inner;
+        let mut byte_len = 0;
+
+        inner.clear();
+
+        // retained chars are shifted back as the string is walked, so they can be indexed as they land
+        self.buf.retain(|c| {
+            let keep = f(c);
+
+            if keep {
+                inner.push_char_at(byte_len, c);
+                byte_len += c.len_utf8();
+            }
+
+            keep
+        });
+    }
+
+    /// Reserves capacity for at least `additional_chars` more chars to be appended, such as with [`push_str`][OwnedIndexedChars::push_str].
+    ///
+    /// The backing `String` reserves one byte per char, which is exact for ascii, and one offset per char is reserved in the index
+    /// unless the string is ascii, as ascii strings store no offsets.
+    /// Rollovers depend on the chars appended, so no space can be reserved for them up front.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let mut index = OwnedIndexedChars::new(String::from("föo"));
+    /// index.reserve(100);
+    ///
+    /// assert!(index.as_string().capacity() >= 104);
+    /// ```
+    pub fn reserve(&mut self, additional_chars: usize) {
+        self.buf.reserve(additional_chars);
+        self.inner.reserve(additional_chars);
+    }
+
+    /// Shrinks the capacity of the backing `String` and of the index to match their length.
+    ///
+    /// Mutating methods such as [`truncate_chars`][OwnedIndexedChars::truncate_chars] keep their allocations around like `String` does,
+    /// this releases the excess memory, as reported by [`memory_footprint`][OwnedIndexedChars::memory_footprint].
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let mut index = OwnedIndexedChars::new("föobär".repeat(10));
+    /// let footprint = index.memory_footprint();
+    ///
+    /// index.truncate_chars(6);
+    /// index.shrink_to_fit();
+    ///
+    /// assert!(index.memory_footprint() < footprint);
+    /// ```
+    pub fn shrink_to_fit(&mut self) {
+        self.buf.shrink_to_fit();
+        self.inner.shrink_to_fit();
+    }
+
+    /// Transforms the backing string with `f`, and rebuilds the index for the string it returns.
+    ///
+    /// The allocations of the old index are reused for the new one, and the index can never be out of sync with the new string.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::OwnedIndexedChars;
+    /// let index = OwnedIndexedChars::new(String::from("FÖO"));
+    /// let lower = index.map_buf(|s| s.to_lowercase());
+    ///
+    /// assert_eq!(lower, "föo");
+    /// assert_eq!(lower.get_char(1), Some('ö'));
+    /// ```
     #[must_use]
-    pub fn char_count(&self) -> usize {
-        self.inner.char_count(&self.buf)
+    pub fn map_buf<F: FnOnce(String) -> String>(self, f: F) -> Self {
+        let utf16 = self.inner.has_utf16_index();
+        let (buf, scratch) = self.into_reuse();
+        let mut this = Self::from_string_reusing(f(buf), scratch);
+
+        // the new string is indexed from scratch, which would drop the UTF-16 index
+        if utf16 {
+            this.inner.index_utf16(&this.buf);
+        }
+
+        this
     }
 
     /// Drops index data and returns backing `String` allocation.
@@ -92,9 +1201,28 @@ impl OwnedIndexedChars {
     }
 }
 
+/// Allocations taken from an [`OwnedIndexedChars`] index, to be reused when building another one.
+///
+/// See [`OwnedIndexedChars::from_string_reusing`] and [`OwnedIndexedChars::into_reuse`].
+#[derive(Debug, Default)]
+pub struct IndexReuse<O = u8> {
+    /// Char offsets allocation
+    chars: Vec<O>,
+    /// Rollovers allocation
+    rollovers: Vec<usize>,
+}
+
+impl IndexReuse {
+    /// Creates a new [`IndexReuse`] with no allocations, for the first build of a reuse loop.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
 // The following lines are all trait implementations made to mirror what str does, and be compatible with str
 
-impl Deref for OwnedIndexedChars {
+impl<O> Deref for OwnedIndexedChars<O> {
     type Target = str;
 
     fn deref(&self) -> &str {
@@ -102,75 +1230,220 @@ impl Deref for OwnedIndexedChars {
     }
 }
 
-impl AsRef<str> for OwnedIndexedChars {
+impl<O> AsRef<str> for OwnedIndexedChars<O> {
     fn as_ref(&self) -> &str {
         self
     }
 }
 
-impl Borrow<str> for OwnedIndexedChars {
+impl<O> AsRef<[u8]> for OwnedIndexedChars<O> {
+    fn as_ref(&self) -> &[u8] {
+        self.buf.as_bytes()
+    }
+}
+
+impl<O> Borrow<str> for OwnedIndexedChars<O> {
     fn borrow(&self) -> &str {
         self
     }
 }
 
-impl fmt::Debug for OwnedIndexedChars {
+impl<O> fmt::Debug for OwnedIndexedChars<O> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         <String as fmt::Debug>::fmt(&self.buf, f)
     }
 }
 
-impl fmt::Display for OwnedIndexedChars {
+impl<O> fmt::Display for OwnedIndexedChars<O> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         <String as fmt::Display>::fmt(&self.buf, f)
     }
 }
 
-impl Eq for OwnedIndexedChars {}
+impl<O> Eq for OwnedIndexedChars<O> {}
 
-impl PartialEq for OwnedIndexedChars {
+impl<O> PartialEq for OwnedIndexedChars<O> {
     fn eq(&self, other: &Self) -> bool {
-        self.buf.eq(&other.buf)
+        self.inner.same_char_count(&other.inner) && self.buf.eq(&other.buf)
     }
 }
 
-impl PartialEq<str> for OwnedIndexedChars {
+impl<O> PartialEq<str> for OwnedIndexedChars<O> {
     fn eq(&self, other: &str) -> bool {
         self.buf.eq(other)
     }
 }
 
-impl PartialEq<OwnedIndexedChars> for str {
-    fn eq(&self, other: &OwnedIndexedChars) -> bool {
+impl<O> PartialEq<OwnedIndexedChars<O>> for str {
+    fn eq(&self, other: &OwnedIndexedChars<O>) -> bool {
         self.eq(&other.buf)
     }
 }
 
-impl Ord for OwnedIndexedChars {
+impl<O, P> PartialEq<IndexedChars<'_, P>> for OwnedIndexedChars<O> {
+    fn eq(&self, other: &IndexedChars<'_, P>) -> bool {
+        other.eq(self)
+    }
+}
+
+impl<O, P> PartialEq<OwnedIndexedChars<O>> for IndexedChars<'_, P> {
+    fn eq(&self, other: &OwnedIndexedChars<O>) -> bool {
+        self.inner_ref().same_char_count(&other.inner) && (**self).eq(&*other.buf)
+    }
+}
+
+impl<O> PartialEq<&str> for OwnedIndexedChars<O> {
+    fn eq(&self, other: &&str) -> bool {
+        self.buf.as_str().eq(*other)
+    }
+}
+
+impl<O> PartialEq<OwnedIndexedChars<O>> for &str {
+    fn eq(&self, other: &OwnedIndexedChars<O>) -> bool {
+        (*self).eq(other.buf.as_str())
+    }
+}
+
+impl<O> PartialEq<String> for OwnedIndexedChars<O> {
+    fn eq(&self, other: &String) -> bool {
+        self.buf.as_str().eq(other.as_str())
+    }
+}
+
+impl<O> PartialEq<OwnedIndexedChars<O>> for String {
+    fn eq(&self, other: &OwnedIndexedChars<O>) -> bool {
+        self.as_str().eq(other.buf.as_str())
+    }
+}
+
+impl<O> Ord for OwnedIndexedChars<O> {
     fn cmp(&self, other: &Self) -> Ordering {
         self.buf.cmp(&other.buf)
     }
 }
 
-impl PartialOrd for OwnedIndexedChars {
+impl<O> PartialOrd for OwnedIndexedChars<O> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
     }
 }
 
-impl PartialOrd<str> for OwnedIndexedChars {
+impl<O> PartialOrd<str> for OwnedIndexedChars<O> {
     fn partial_cmp(&self, other: &str) -> Option<Ordering> {
         Some((*self.buf).cmp(other))
     }
 }
 
-impl PartialOrd<OwnedIndexedChars> for str {
-    fn partial_cmp(&self, other: &OwnedIndexedChars) -> Option<Ordering> {
+impl<O> PartialOrd<OwnedIndexedChars<O>> for str {
+    fn partial_cmp(&self, other: &OwnedIndexedChars<O>) -> Option<Ordering> {
         Some(self.cmp(&other.buf))
     }
 }
 
-impl Hash for OwnedIndexedChars {
+impl<O> PartialOrd<&str> for OwnedIndexedChars<O> {
+    fn partial_cmp(&self, other: &&str) -> Option<Ordering> {
+        Some(self.buf.as_str().cmp(*other))
+    }
+}
+
+impl<O> PartialOrd<OwnedIndexedChars<O>> for &str {
+    fn partial_cmp(&self, other: &OwnedIndexedChars<O>) -> Option<Ordering> {
+        Some((*self).cmp(other.buf.as_str()))
+    }
+}
+
+impl<O> PartialOrd<String> for OwnedIndexedChars<O> {
+    fn partial_cmp(&self, other: &String) -> Option<Ordering> {
+        Some(self.buf.cmp(other))
+    }
+}
+
+impl<O> PartialOrd<OwnedIndexedChars<O>> for String {
+    fn partial_cmp(&self, other: &OwnedIndexedChars<O>) -> Option<Ordering> {
+        Some(self.cmp(&other.buf))
+    }
+}
+
+impl<O, P> PartialOrd<IndexedChars<'_, P>> for OwnedIndexedChars<O> {
+    fn partial_cmp(&self, other: &IndexedChars<'_, P>) -> Option<Ordering> {
+        Some((*self.buf).cmp(&**other))
+    }
+}
+
+impl<O, P> PartialOrd<OwnedIndexedChars<O>> for IndexedChars<'_, P> {
+    fn partial_cmp(&self, other: &OwnedIndexedChars<O>) -> Option<Ordering> {
+        Some((**self).cmp(&*other.buf))
+    }
+}
+
+impl<O: OffsetInt> Default for OwnedIndexedChars<O> {
+    fn default() -> Self {
+        Self::new_with_width(String::new())
+    }
+}
+
+impl<O: OffsetInt> FromIterator<char> for OwnedIndexedChars<O> {
+    fn from_iter<I: IntoIterator<Item = char>>(iter: I) -> Self {
+        // extending indexes each char as it is appended, instead of scanning the string again afterwards
+        let mut this = Self::default();
+        this.extend(iter);
+
+        this
+    }
+}
+
+impl<'a, O: OffsetInt> FromIterator<&'a str> for OwnedIndexedChars<O> {
+    fn from_iter<I: IntoIterator<Item = &'a str>>(iter: I) -> Self {
+        // each piece is indexed as it is appended, instead of scanning the joined string again afterwards
+        let mut this = Self::default();
+        this.extend(iter);
+
+        this
+    }
+}
+
+impl<O: OffsetInt> Extend<char> for OwnedIndexedChars<O> {
+    fn extend<I: IntoIterator<Item = char>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+
+        self.buf.reserve(iter.size_hint().0);
+
+        for c in iter {
+            self.push(c);
+        }
+    }
+}
+
+impl<'a, O: OffsetInt> Extend<&'a str> for OwnedIndexedChars<O> {
+    fn extend<I: IntoIterator<Item = &'a str>>(&mut self, iter: I) {
+        for s in iter {
+            self.push_str(s);
+        }
+    }
+}
+
+impl<'a, O: OffsetInt> From<Cow<'a, str>> for OwnedIndexedChars<O> {
+    fn from(s: Cow<'a, str>) -> Self {
+        // only copies the string if it was borrowed
+        Self::new_with_width(s.into_owned())
+    }
+}
+
+impl<O: OffsetInt> FromStr for OwnedIndexedChars<O> {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Infallible> {
+        Ok(Self::new_with_width(String::from(s)))
+    }
+}
+
+impl<O> From<OwnedIndexedChars<O>> for String {
+    fn from(index: OwnedIndexedChars<O>) -> Self {
+        index.buf
+    }
+}
+
+impl<O> Hash for OwnedIndexedChars<O> {
     fn hash<H: Hasher>(&self, state: &mut H) {
         self.buf.hash(state);
     }
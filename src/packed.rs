@@ -0,0 +1,290 @@
+//! Module containing [`PackedIndexedChars`] and its trait implementations
+
+use alloc::vec::Vec;
+use core::{
+    borrow::Borrow,
+    cmp::{Eq, PartialEq},
+    fmt,
+    hash::{Hash, Hasher},
+    mem,
+    ops::{Deref, Range},
+};
+
+/// Amount of chars whose widths are packed into a single word, at 2 bits each.
+const CHARS_PER_WORD: usize = 32;
+
+/// Amount of words covered by each stored base offset, a lookup sums at most this many words.
+const WORDS_PER_BLOCK: usize = 8;
+
+/// Mask of the low bit of every 2 bit field in a word.
+const LOW_BITS: u64 = 0x5555_5555_5555_5555;
+
+/// A string whose char indices have been cached for O(1) char lookup, storing its offsets bit packed.
+///
+/// Every char of a utf8 string takes between 1 and 4 bytes, so the amount of bytes it takes past the first
+/// always fits in 2 bits. This index stores exactly that for every char, 32 chars to a `u64`,
+/// along with the total offset at the start of every block of 256 chars.
+/// A lookup adds the block's offset to the widths of the chars before it in the block, which are summed with popcounts.
+///
+/// This takes 2.25 bits per char, where [`IndexedChars`](crate::IndexedChars) takes 8 bits per char plus its rollovers,
+/// and lookups are O(1) for any string, as nothing ever rolls over. In exchange each lookup does a little more work,
+/// so this is best suited for large strings that are kept around, where the index memory matters.
+/// Ascii strings use the same niche as [`IndexedChars`](crate::IndexedChars), and do not allocate.
+///
+/// Only available with the `packed` feature enabled.
+///
+/// This type mimics a `&'a str` with its trait impls, including `Debug`, `Display`, `PartialEq` with `&str`, `Hash`, and `AsRef`/`Borrow`.
+///
+/// # Examples
+/// ```rust
+/// # use char_index::{IndexedChars, PackedIndexedChars};
+/// let s = "föobär".repeat(100);
+/// let index = PackedIndexedChars::new(&s);
+///
+/// assert_eq!(index.get_char(598), Some('ä'));
+/// assert_eq!(index.get_str(4..7), Some("ärf"));
+/// assert!(index.memory_footprint() * 3 < IndexedChars::new(&s).memory_footprint());
+/// ```
+#[derive(Clone)]
+pub struct PackedIndexedChars<'a> {
+    /// Backing string buffer
+    buf: &'a str,
+    /// Bytes past the first taken by every char, 2 bits each starting from the least significant bits, empty for ascii strings
+    widths: Vec<u64>,
+    /// Total offset of the first char of every block of [`WORDS_PER_BLOCK`] words
+    bases: Vec<usize>,
+    /// The amount of chars in the backing string
+    char_len: usize,
+}
+
+impl<'a> PackedIndexedChars<'a> {
+    /// Constructs a new [`PackedIndexedChars`] instance from a [`&str`]. This is O(n), but the cost should only be paid once ideally.
+    #[must_use]
+    pub fn new(s: &'a str) -> Self {
+        if s.is_ascii() {
+            return Self {
+                buf: s,
+                widths: Vec::new(),
+                bases: Vec::new(),
+                char_len: s.len(),
+            };
+        }
+
+        let mut widths = Vec::with_capacity(s.len() / CHARS_PER_WORD + 1);
+        let mut bases = Vec::with_capacity(s.len() / (CHARS_PER_WORD * WORDS_PER_BLOCK) + 1);
+        let mut word = 0;
+        let mut char_len = 0;
+
+        for (byte_idx, c) in s.char_indices() {
+            let slot = char_len % CHARS_PER_WORD;
+
+            if slot == 0 {
+                if char_len != 0 {
+                    widths.push(word);
+                    word = 0;
+                }
+
+                if widths.len() % WORDS_PER_BLOCK == 0 {
+                    bases.push(byte_idx - char_len);
+                }
+            }
+
+            word |= (c.len_utf8() as u64 - 1) << (slot * 2);
+            char_len += 1;
+        }
+
+        widths.push(word);
+        widths.shrink_to_fit();
+        bases.shrink_to_fit();
+
+        Self {
+            buf: s,
+            widths,
+            bases,
+            char_len,
+        }
+    }
+
+    /// Returns `true` if the backing string is ascii, in which case no offsets are stored.
+    fn is_ascii(&self) -> bool {
+        self.widths.is_empty()
+    }
+
+    /// Computes the offset of the char at `index`, which must be less than the char count of a non ascii string.
+    fn offset(&self, index: usize) -> usize {
+        let word = index / CHARS_PER_WORD;
+        let block = word / WORDS_PER_BLOCK;
+
+        let full: usize = self.widths[block * WORDS_PER_BLOCK..word]
+            .iter()
+            .map(|&w| sum_widths(w))
+            .sum();
+
+        // keep only the fields of the chars before index, the shift is at most 62
+        let mask = (1 << ((index % CHARS_PER_WORD) * 2)) - 1;
+
+        self.bases[block] + full + sum_widths(self.widths[word] & mask)
+    }
+
+    /// Indexes into the backing string to retrieve the nth codepoint.
+    ///
+    /// This operation is O(1).
+    #[must_use]
+    pub fn get_char(&self, index: usize) -> Option<char> {
+        let byte_index = self.char_to_byte(index)?;
+
+        self.buf[byte_index..].chars().next()
+    }
+
+    /// Converts a char index into the byte index where that char starts in the backing string.
+    ///
+    /// Returns `None` if the index is out of bounds.
+    #[must_use]
+    pub fn char_to_byte(&self, index: usize) -> Option<usize> {
+        if index >= self.char_len {
+            None
+        } else if self.is_ascii() {
+            Some(index)
+        } else {
+            Some(index + self.offset(index))
+        }
+    }
+
+    /// Gets the substring of the backing string spanning the chars in `range`.
+    ///
+    /// Returns `None` if the range is reversed or out of bounds.
+    #[must_use]
+    pub fn get_str(&self, range: Range<usize>) -> Option<&'a str> {
+        if range.start > range.end || range.end > self.char_len {
+            return None;
+        }
+
+        // the index one past the last char is the end of the string
+        let boundary = |index| self.char_to_byte(index).unwrap_or(self.buf.len());
+
+        Some(&self.buf[boundary(range.start)..boundary(range.end)])
+    }
+
+    /// Returns the number of chars in the backing string in O(1).
+    #[must_use]
+    pub fn char_count(&self) -> usize {
+        self.char_len
+    }
+
+    /// Returns `true` if the backing string is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.buf.is_empty()
+    }
+
+    /// Returns the amount of heap memory in bytes used by the index, not counting the backing string.
+    #[must_use]
+    pub fn memory_footprint(&self) -> usize {
+        self.widths.capacity() * mem::size_of::<u64>()
+            + self.bases.capacity() * mem::size_of::<usize>()
+    }
+
+    /// Returns a reference to the backing `&str`
+    #[must_use]
+    pub fn as_str(&self) -> &'a str {
+        self.buf
+    }
+}
+
+/// Sums the 2 bit fields of a word of packed widths.
+fn sum_widths(word: u64) -> usize {
+    // each field is worth its low bit plus twice its high bit
+    (word & LOW_BITS).count_ones() as usize + 2 * (word & !LOW_BITS).count_ones() as usize
+}
+
+// The following lines are all trait implementations made to mirror what str does, and be compatible with str
+
+impl Deref for PackedIndexedChars<'_> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.buf
+    }
+}
+
+impl AsRef<str> for PackedIndexedChars<'_> {
+    fn as_ref(&self) -> &str {
+        self
+    }
+}
+
+impl Borrow<str> for PackedIndexedChars<'_> {
+    fn borrow(&self) -> &str {
+        self
+    }
+}
+
+impl fmt::Debug for PackedIndexedChars<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        <str as fmt::Debug>::fmt(self.buf, f)
+    }
+}
+
+impl fmt::Display for PackedIndexedChars<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        <str as fmt::Display>::fmt(self.buf, f)
+    }
+}
+
+impl Eq for PackedIndexedChars<'_> {}
+
+impl PartialEq for PackedIndexedChars<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.buf.eq(other.buf)
+    }
+}
+
+impl PartialEq<str> for PackedIndexedChars<'_> {
+    fn eq(&self, other: &str) -> bool {
+        self.buf.eq(other)
+    }
+}
+
+impl PartialEq<&str> for PackedIndexedChars<'_> {
+    fn eq(&self, other: &&str) -> bool {
+        self.buf.eq(*other)
+    }
+}
+
+impl Hash for PackedIndexedChars<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.buf.hash(state);
+    }
+}
+
+#[test]
+fn packed() {
+    use alloc::string::String;
+
+    fn check(s: &str) {
+        let index = PackedIndexedChars::new(s);
+
+        assert_eq!(index.char_count(), s.chars().count());
+
+        for (i, (byte_idx, c)) in s.char_indices().enumerate() {
+            assert_eq!(index.get_char(i), Some(c));
+            assert_eq!(index.char_to_byte(i), Some(byte_idx));
+        }
+
+        assert_eq!(index.get_char(index.char_count()), None);
+        assert_eq!(index.get_str(0..index.char_count()), Some(s));
+    }
+
+    check("");
+    check("foobar");
+    check("föobär");
+    check(&"💯".repeat(300));
+
+    // cross word and block boundaries with every char width
+    let mixed: String = "aö€💯".repeat(200);
+    check(&mixed);
+    check(&mixed[1..]);
+    check(&"föobär".repeat(1000));
+
+    assert_eq!(PackedIndexedChars::new("foobar").memory_footprint(), 0);
+}
@@ -0,0 +1,37 @@
+//! Module containing the optional `serde` trait implementations,
+//! both types serialize as their backing string, and the index is rebuilt on deserialization.
+
+use alloc::string::String;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{offset::OffsetInt, IndexedChars, OwnedIndexedChars};
+
+impl<O: OffsetInt> Serialize for IndexedChars<'_, O> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<O: OffsetInt> Serialize for OwnedIndexedChars<O> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de, O: OffsetInt> Deserialize<'de> for OwnedIndexedChars<O> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(OwnedIndexedChars::new_with_width)
+    }
+}
+
+#[test]
+fn deserialize() {
+    use serde::de::{value, IntoDeserializer};
+
+    let de: value::StrDeserializer<value::Error> = "föobär".into_deserializer();
+
+    let index: OwnedIndexedChars = OwnedIndexedChars::deserialize(de).unwrap();
+
+    assert_eq!(index.as_str(), "föobär");
+    assert_eq!(index.get_char(4), Some('ä'));
+}
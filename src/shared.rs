@@ -0,0 +1,705 @@
+//! Module containing [`SharedIndexedChars`] and its trait implementations
+
+use alloc::{sync::Arc, vec::Vec};
+use core::{
+    borrow::Borrow,
+    cmp::{Eq, Ord, Ordering, PartialEq, PartialOrd},
+    fmt,
+    hash::{Hash, Hasher},
+    iter::Rev,
+    ops::{Deref, Range},
+};
+
+use crate::{
+    offset::OffsetInt, CharChunks, CharCursor, CharIndex, CharIndexError, CharIndices, CharWindows,
+    Chars, IndexedChars, IndexedCharsInner, OwnedIndexedChars,
+};
+
+#[cfg(feature = "unicode-normalization")]
+use alloc::borrow::Cow;
+
+/// A string whose char indices have been cached for ~O(1) char lookup. Variant over any string pointer.
+///
+/// This behaves like [`OwnedIndexedChars`](crate::OwnedIndexedChars), but its backing string can be any `S: Deref<Target = str>`,
+/// such as `Rc<str>`, `Arc<str>`, or `Box<str>`, so an already shared string does not have to be copied into a `String` to be indexed.
+///
+/// The index is kept behind an `Arc`, so cloning this type clones `S` and shares the index,
+/// which makes clones O(1) for reference counted strings such as `Rc<str>` or `Arc<str>`.
+///
+/// This type mimics a `str` with its trait impls, including `Clone`, `Debug`, `Display`, `PartialEq` with `&str` `PartialOrd` with `&str`, `Hash`, `AsRef<str>`/`AsRef<[u8]>`, and `Borrow`.
+///
+/// Lookups go through [`as_indexed`][SharedIndexedChars::as_indexed], and are documented with examples on [`IndexedChars`].
+#[derive(Clone)]
+pub struct SharedIndexedChars<S, O = u8> {
+    /// Backing string pointer
+    buf: S,
+    /// Char offsets index, shared between clones
+    inner: Arc<IndexedCharsInner<O>>,
+}
+
+impl<S: Deref<Target = str>> SharedIndexedChars<S> {
+    /// Constructs a new [`SharedIndexedChars`] instance from a string pointer. This is O(n), but the cost should only be paid once ideally.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use char_index::SharedIndexedChars;
+    /// use std::sync::Arc;
+    ///
+    /// let s: Arc<str> = Arc::from("föo");
+    /// let index = SharedIndexedChars::new(Arc::clone(&s));
+    ///
+    /// assert_eq!(index.get_char(1), Some('ö'));
+    /// ```
+    #[must_use]
+    pub fn new(s: S) -> Self {
+        Self::new_with_width(s)
+    }
+
+    /// Constructs a new [`SharedIndexedChars`] instance with a UTF-16 index, see [`IndexedChars::with_utf16_index`].
+    #[must_use]
+    pub fn with_utf16_index(s: S) -> Self {
+        let mut inner = IndexedCharsInner::new(&s);
+        inner.index_utf16(&s);
+
+        Self {
+            buf: s,
+            inner: Arc::new(inner),
+        }
+    }
+}
+
+impl<S: Deref<Target = str>, O: OffsetInt> SharedIndexedChars<S, O> {
+    /// Constructs a new [`SharedIndexedChars`] instance from a string pointer, storing its offsets in `O`.
+    ///
+    /// This is the same as [`new`][SharedIndexedChars::new] for any offset width.
+    #[must_use]
+    pub fn new_with_width(s: S) -> Self {
+        let inner = Arc::new(IndexedCharsInner::new(&s));
+
+        Self { buf: s, inner }
+    }
+
+    /// Constructs a new [`SharedIndexedChars`] instance from a string pointer whose char count is already known,
+    /// skipping the counting pass [`new`][SharedIndexedChars::new] makes, see [`IndexedChars::new_with_char_count`].
+    #[must_use]
+    pub fn new_with_char_count(s: S, char_count: usize) -> Self {
+        let inner = Arc::new(IndexedCharsInner::new_with_char_count(&s, char_count));
+
+        Self { buf: s, inner }
+    }
+
+    /// Indexes into the backing string to retrieve the nth codepoint.
+    ///
+    /// See [`IndexedChars::get_char`].
+    #[must_use]
+    pub fn get_char(&self, index: usize) -> Option<char> {
+        self.as_indexed().get_char(index)
+    }
+
+    /// Indexes into the backing string to retrieve the nth codepoint, or returns `default` if the index is out of bounds.
+    ///
+    /// See [`IndexedChars::get_char_or`].
+    #[must_use]
+    pub fn get_char_or(&self, index: usize, default: char) -> char {
+        self.as_indexed().get_char_or(index, default)
+    }
+
+    /// Indexes into the backing string to retrieve the nth char, alongside the byte range it spans in the backing string.
+    ///
+    /// See [`IndexedChars::char_and_span`].
+    #[must_use]
+    pub fn char_and_span(&self, index: usize) -> Option<(char, Range<usize>)> {
+        self.as_indexed().char_and_span(index)
+    }
+
+    /// Returns `true` if the char at `index` is `c`, and `false` if it is not or the index is out of bounds.
+    ///
+    /// See [`IndexedChars::nth_char_is`].
+    #[must_use]
+    pub fn nth_char_is(&self, index: usize, c: char) -> bool {
+        self.as_indexed().nth_char_is(index, c)
+    }
+
+    /// Retrieves the chars at several char indices at once, as if by calling [`get_char`][SharedIndexedChars::get_char] for each of them.
+    ///
+    /// See [`IndexedChars::get_many`].
+    #[must_use]
+    pub fn get_many<const N: usize>(&self, indices: [usize; N]) -> [Option<char>; N] {
+        self.as_indexed().get_many(indices)
+    }
+
+    /// Returns a [`CharCursor`] at the start of the backing string, for looking up chars near each other faster than [`get_char`][SharedIndexedChars::get_char].
+    #[must_use]
+    pub fn cursor(&self) -> CharCursor<'_, O> {
+        CharCursor::new(&self.buf, &self.inner)
+    }
+
+    /// Indexes into the backing string to retrieve the nth codepoint, panicking if it is out of bounds.
+    ///
+    /// See [`IndexedChars::char_at`].
+    ///
+    /// # Panics
+    /// Panics if `index` is not less than [`char_count`][SharedIndexedChars::char_count].
+    #[must_use]
+    #[track_caller]
+    pub fn char_at(&self, index: usize) -> char {
+        self.as_indexed().char_at(index)
+    }
+
+    /// Returns the first char of the backing string, or `None` if it is empty.
+    ///
+    /// See [`IndexedChars::first_char`].
+    #[must_use]
+    pub fn first_char(&self) -> Option<char> {
+        self.as_indexed().first_char()
+    }
+
+    /// Returns the last char of the backing string in O(1), or `None` if it is empty.
+    ///
+    /// See [`IndexedChars::last_char`].
+    #[must_use]
+    pub fn last_char(&self) -> Option<char> {
+        self.as_indexed().last_char()
+    }
+
+    /// Converts a char index into the byte index where that char starts in the backing string.
+    ///
+    /// See [`IndexedChars::char_to_byte`].
+    #[must_use]
+    pub fn char_to_byte(&self, index: usize) -> Option<usize> {
+        self.as_indexed().char_to_byte(index)
+    }
+
+    /// Returns an iterator over the byte index where each char starts in the backing string, in char order.
+    ///
+    /// This yields what [`char_to_byte`][SharedIndexedChars::char_to_byte] returns for every index,
+    /// but walks the index once instead of searching it for each char.
+    pub fn char_offsets(&self) -> impl Iterator<Item = usize> + '_ {
+        self.inner.char_offsets()
+    }
+
+    /// Returns the byte index of the boundary before the nth char, suitable for passing to [`str::split_at`].
+    ///
+    /// See [`IndexedChars::nth_char_boundary`].
+    #[must_use]
+    pub fn nth_char_boundary(&self, char_index: usize) -> Option<usize> {
+        self.as_indexed().nth_char_boundary(char_index)
+    }
+
+    /// Divides the backing string into two substrings at a char index, like [`str::split_at`] but indexed by chars.
+    ///
+    /// See [`IndexedChars::split_at_char`].
+    #[must_use]
+    pub fn split_at_char(&self, char_index: usize) -> Option<(&str, &str)> {
+        self.as_indexed().split_at_char(char_index)
+    }
+
+    /// Checks that the backing string is equal to `other`, ignoring ascii case, without allocating.
+    ///
+    /// See [`IndexedChars::eq_ignore_ascii_case`].
+    #[must_use]
+    pub fn eq_ignore_ascii_case(&self, other: &str) -> bool {
+        self.as_indexed().eq_ignore_ascii_case(other)
+    }
+
+    /// Returns `true` if the chars of the backing string are exactly the chars yielded by `iter`, without collecting them into a string.
+    ///
+    /// See [`IndexedChars::eq_chars`].
+    #[must_use]
+    pub fn eq_chars<I: IntoIterator<Item = char>>(&self, iter: I) -> bool {
+        self.as_indexed().eq_chars(iter)
+    }
+
+    /// Returns the backing string in Unicode Normalization Form C, borrowing it if it is already normalized.
+    ///
+    /// See [`IndexedChars::normalized`].
+    #[cfg(feature = "unicode-normalization")]
+    #[must_use]
+    pub fn normalized(&self) -> Cow<'_, str> {
+        self.as_indexed().normalized()
+    }
+
+    /// Converts a byte index into the index of the char that starts at that byte in the backing string.
+    ///
+    /// See [`IndexedChars::byte_to_char`].
+    #[must_use]
+    pub fn byte_to_char(&self, byte_index: usize) -> Option<usize> {
+        self.as_indexed().byte_to_char(byte_index)
+    }
+
+    /// Returns the number of chars in the substring spanning the bytes in `range`, without scanning it.
+    ///
+    /// See [`IndexedChars::char_count_in_range`].
+    #[must_use]
+    pub fn char_count_in_range(&self, range: Range<usize>) -> Option<usize> {
+        self.as_indexed().char_count_in_range(range)
+    }
+
+    /// Converts a char index into its zero based `(line, column)` position, where lines are separated by `\n` and columns count chars.
+    ///
+    /// See [`IndexedChars::line_col`].
+    #[must_use]
+    pub fn line_col(&self, char_index: usize) -> Option<(usize, usize)> {
+        self.as_indexed().line_col(char_index)
+    }
+
+    /// Returns the width of the backing string when displayed in a terminal, in columns, as the sum of the widths of its chars.
+    ///
+    /// See [`IndexedChars::display_width`].
+    #[cfg(feature = "unicode-width")]
+    #[must_use]
+    pub fn display_width(&self) -> usize {
+        self.as_indexed().display_width()
+    }
+
+    /// Converts a display column into the index of the char covering it, such as for placing a cursor at a terminal column.
+    ///
+    /// See [`IndexedChars::width_to_char`].
+    #[cfg(feature = "unicode-width")]
+    #[must_use]
+    pub fn width_to_char(&self, column: usize) -> Option<usize> {
+        self.as_indexed().width_to_char(column)
+    }
+
+    /// Converts a char index into the index of its first UTF-16 code unit, for interop with UTF-16 based APIs.
+    ///
+    /// See [`IndexedChars::char_to_utf16`].
+    #[must_use]
+    pub fn char_to_utf16(&self, index: usize) -> Option<usize> {
+        self.as_indexed().char_to_utf16(index)
+    }
+
+    /// Converts a UTF-16 code unit index into a char index, for interop with UTF-16 based APIs.
+    ///
+    /// See [`IndexedChars::utf16_to_char`].
+    #[must_use]
+    pub fn utf16_to_char(&self, utf16_index: usize) -> Option<usize> {
+        self.as_indexed().utf16_to_char(utf16_index)
+    }
+
+    /// Returns the char index of the first occurrence of `c` in the backing string, or `None` if it does not occur.
+    ///
+    /// See [`IndexedChars::find_char`].
+    #[must_use]
+    pub fn find_char(&self, c: char) -> Option<usize> {
+        self.as_indexed().find_char(c)
+    }
+
+    /// Returns the char index of the last occurrence of `c` in the backing string, or `None` if it does not occur.
+    ///
+    /// See [`IndexedChars::rfind_char`].
+    #[must_use]
+    pub fn rfind_char(&self, c: char) -> Option<usize> {
+        self.as_indexed().rfind_char(c)
+    }
+
+    /// Returns the char index where `pat` first occurs in the backing string, or `None` if it does not occur.
+    ///
+    /// See [`IndexedChars::find_str`].
+    #[must_use]
+    pub fn find_str(&self, pat: &str) -> Option<usize> {
+        self.as_indexed().find_str(pat)
+    }
+
+    /// Returns the number of occurrences of `c` in the backing string.
+    ///
+    /// See [`IndexedChars::count_matches`].
+    #[must_use]
+    pub fn count_matches(&self, c: char) -> usize {
+        self.as_indexed().count_matches(c)
+    }
+
+    /// Returns `true` if `c` occurs in the backing string.
+    ///
+    /// See [`IndexedChars::contains_char`].
+    #[must_use]
+    pub fn contains_char(&self, c: char) -> bool {
+        self.as_indexed().contains_char(c)
+    }
+
+    /// Gets the substring of the backing string spanning the chars in `range`.
+    ///
+    /// See [`IndexedChars::get_str`].
+    #[must_use]
+    pub fn get_str(&self, range: Range<usize>) -> Option<&str> {
+        self.as_indexed().get_str(range)
+    }
+
+    /// Converts a range of chars into the range of bytes spanning them in the backing string, for slicing its bytes by char bounds.
+    ///
+    /// See [`IndexedChars::char_to_byte_range`].
+    #[must_use]
+    pub fn char_to_byte_range(&self, char_range: Range<usize>) -> Option<Range<usize>> {
+        self.as_indexed().char_to_byte_range(char_range)
+    }
+
+    /// Same as [`get_str`][SharedIndexedChars::get_str], but returns why the range could not be looked up instead of `None`.
+    ///
+    /// See [`IndexedChars::try_get_str`].
+    ///
+    /// # Errors
+    /// Returns [`CharIndexError::InvalidRange`] if the range is reversed, and [`CharIndexError::OutOfBounds`] if it reaches past the last char.
+    pub fn try_get_str(&self, range: Range<usize>) -> Result<&str, CharIndexError> {
+        self.as_indexed().try_get_str(range)
+    }
+
+    /// Same as [`char_to_byte_range`][SharedIndexedChars::char_to_byte_range], but returns why the range could not be looked up instead of `None`.
+    ///
+    /// See [`IndexedChars::try_char_to_byte_range`].
+    ///
+    /// # Errors
+    /// Returns [`CharIndexError::InvalidRange`] if the range is reversed, and [`CharIndexError::OutOfBounds`] if it reaches past the last char.
+    pub fn try_char_to_byte_range(
+        &self,
+        char_range: Range<usize>,
+    ) -> Result<Range<usize>, CharIndexError> {
+        self.as_indexed().try_char_to_byte_range(char_range)
+    }
+
+    /// Returns the length of the backing string in bytes, this is the same as [`str::len`].
+    ///
+    /// See [`IndexedChars::byte_len`].
+    #[must_use]
+    pub fn byte_len(&self) -> usize {
+        self.as_indexed().byte_len()
+    }
+
+    /// Looks up a char or a substring by char index, depending on the type of `index`, like [`slice::get`].
+    ///
+    /// A `usize` retrieves the char at that index like [`get_char`][SharedIndexedChars::get_char],
+    /// and a range retrieves the substring spanning those chars like [`get_str`][SharedIndexedChars::get_str], see [`CharIndex`].
+    /// Note that `str::get`, reachable through `Deref`, takes a range of bytes instead.
+    #[must_use]
+    pub fn get_at<'s, I: CharIndex<'s>>(&'s self, index: I) -> Option<I::Output> {
+        index.get_in(&self.as_indexed())
+    }
+
+    /// Returns a value that displays the chars in `range` without allocating, for logging part of a large string.
+    ///
+    /// See [`IndexedChars::display_range`].
+    #[must_use]
+    pub fn display_range(&self, range: Range<usize>) -> impl fmt::Display + '_ {
+        self.as_indexed().display_range(range)
+    }
+
+    /// Returns a view of this string with leading and trailing whitespace removed, as with [`str::trim`], keeping it indexed.
+    ///
+    /// The view is created as with [`subindex`][IndexedChars::subindex], so no rescan of the string is needed.
+    /// This is not named `trim` so as to not shadow [`str::trim`], which stays available through `Deref`.
+    #[must_use]
+    pub fn trim_indexed(&self) -> IndexedChars<'_, O> {
+        self.as_indexed().trim_indexed()
+    }
+
+    /// Same as [`trim_indexed`][SharedIndexedChars::trim_indexed], but only removes leading whitespace, as with [`str::trim_start`].
+    #[must_use]
+    pub fn trim_start_indexed(&self) -> IndexedChars<'_, O> {
+        self.as_indexed().trim_start_indexed()
+    }
+
+    /// Same as [`trim_indexed`][SharedIndexedChars::trim_indexed], but only removes trailing whitespace, as with [`str::trim_end`].
+    #[must_use]
+    pub fn trim_end_indexed(&self) -> IndexedChars<'_, O> {
+        self.as_indexed().trim_end_indexed()
+    }
+
+    /// Gets the substring of the backing string spanning the chars in `range`, like [`get_str`][SharedIndexedChars::get_str] but panicking instead of returning `None`.
+    ///
+    /// See [`IndexedChars::index_chars`].
+    ///
+    /// # Panics
+    /// Panics if the range is reversed or its end is greater than [`len_chars`][SharedIndexedChars::len_chars].
+    #[must_use]
+    #[track_caller]
+    pub fn index_chars(&self, range: Range<usize>) -> &str {
+        self.as_indexed().index_chars(range)
+    }
+
+    /// Borrows this instance as an [`IndexedChars`](crate::IndexedChars), this is O(1) as the index is shared rather than rebuilt.
+    ///
+    /// `S` is not required to deref to the same string every time, so unchecked lookups on the view are checked anyway.
+    #[must_use]
+    pub fn as_indexed(&self) -> IndexedChars<'_, O> {
+        IndexedChars::from_unverified(&self.buf, &self.inner)
+    }
+
+    /// Returns the number of chars present in the backing string, this operation is free thanks to
+    /// how [`SharedIndexedChars`] is constructed
+    ///
+    /// See [`IndexedChars::char_count`].
+    #[must_use]
+    pub fn char_count(&self) -> usize {
+        self.as_indexed().char_count()
+    }
+
+    /// Returns the number of chars (unicode scalar values) present in the backing string in O(1).
+    ///
+    /// See [`IndexedChars::len_chars`].
+    #[must_use]
+    pub fn len_chars(&self) -> usize {
+        self.as_indexed().len_chars()
+    }
+
+    /// Returns `true` if the backing string is empty.
+    ///
+    /// See [`IndexedChars::is_empty`].
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.as_indexed().is_empty()
+    }
+
+    /// Returns `true` if the ascii optimization is active, which is the case exactly when the backing string is pure ascii.
+    ///
+    /// See [`IndexedChars::is_ascii`].
+    #[must_use]
+    pub fn is_ascii(&self) -> bool {
+        self.as_indexed().is_ascii()
+    }
+
+    /// Returns the amount of rollovers that occurred while building the index.
+    ///
+    /// See [`IndexedChars::rollover_count`].
+    #[must_use]
+    pub fn rollover_count(&self) -> usize {
+        self.as_indexed().rollover_count()
+    }
+
+    /// Returns the amount of heap memory in bytes used by the index, not counting the backing string.
+    ///
+    /// See [`IndexedChars::memory_footprint`].
+    #[must_use]
+    pub fn memory_footprint(&self) -> usize {
+        self.as_indexed().memory_footprint()
+    }
+
+    /// Returns the amount of char offsets the index can hold without reallocating.
+    ///
+    /// See [`IndexedChars::capacity_chars`].
+    #[must_use]
+    pub fn capacity_chars(&self) -> usize {
+        self.as_indexed().capacity_chars()
+    }
+
+    /// Returns the amount of rollover points the index can hold without reallocating.
+    ///
+    /// See [`IndexedChars::capacity_rollovers`].
+    #[must_use]
+    pub fn capacity_rollovers(&self) -> usize {
+        self.as_indexed().capacity_rollovers()
+    }
+
+    /// Returns an iterator over the chars of the backing string.
+    ///
+    /// See [`IndexedChars::chars`].
+    #[must_use]
+    pub fn chars(&self) -> Chars<'_> {
+        self.as_indexed().chars()
+    }
+
+    /// Clears `out` and fills it with the chars of the backing string, reusing its allocation.
+    ///
+    /// See [`IndexedChars::collect_chars_into`].
+    pub fn collect_chars_into(&self, out: &mut Vec<char>) {
+        self.as_indexed().collect_chars_into(out);
+    }
+
+    /// Returns an iterator over the chars of the backing string, starting at the char at `index`.
+    ///
+    /// See [`IndexedChars::chars_from`].
+    #[must_use]
+    pub fn chars_from(&self, index: usize) -> Option<Chars<'_>> {
+        self.as_indexed().chars_from(index)
+    }
+
+    /// Returns an iterator over the chars of the backing string in reverse order, from last to first.
+    ///
+    /// See [`IndexedChars::rchars`].
+    pub fn rchars(&self) -> Rev<Chars<'_>> {
+        self.as_indexed().rchars()
+    }
+
+    /// Returns a new [`OwnedIndexedChars`] of the chars of the backing string in reverse order.
+    ///
+    /// See [`IndexedChars::reversed`].
+    #[must_use]
+    pub fn reversed(&self) -> OwnedIndexedChars<O> {
+        self.as_indexed().reversed()
+    }
+
+    /// Returns an iterator over the chars of the backing string and their positions, as `(char_index, byte_index, char)`.
+    ///
+    /// See [`IndexedChars::char_indices`].
+    #[must_use]
+    pub fn char_indices(&self) -> CharIndices<'_> {
+        self.as_indexed().char_indices()
+    }
+
+    /// Returns an iterator over the chars of the backing string and the byte index each starts at, in order.
+    ///
+    /// See [`IndexedChars::positions`].
+    #[must_use]
+    pub fn positions(
+        &self,
+    ) -> impl ExactSizeIterator<Item = (char, usize)> + DoubleEndedIterator + '_ {
+        self.as_indexed().positions()
+    }
+
+    /// Returns an iterator over the chars of the backing string and their char indices in reverse order, from last to first.
+    ///
+    /// See [`IndexedChars::rchar_indices`].
+    #[must_use]
+    pub fn rchar_indices(
+        &self,
+    ) -> impl ExactSizeIterator<Item = (usize, char)> + DoubleEndedIterator + '_ {
+        self.as_indexed().rchar_indices()
+    }
+
+    /// Returns an iterator over all overlapping windows of `size` chars in the backing string, as string slices.
+    ///
+    /// See [`IndexedChars::char_windows`].
+    ///
+    /// # Panics
+    /// Panics if `size` is 0.
+    #[must_use]
+    pub fn char_windows(&self, size: usize) -> CharWindows<'_> {
+        self.as_indexed().char_windows(size)
+    }
+
+    /// Returns an iterator over non overlapping chunks of `size` chars in the backing string, as string slices.
+    ///
+    /// See [`IndexedChars::char_chunks`].
+    ///
+    /// # Panics
+    /// Panics if `size` is 0.
+    #[must_use]
+    pub fn char_chunks(&self, size: usize) -> CharChunks<'_> {
+        self.as_indexed().char_chunks(size)
+    }
+
+    /// Returns an iterator over the substrings of the backing string separated by `pat`, each paired with the char index it starts at.
+    ///
+    /// See [`IndexedChars::split_char_indices`].
+    pub fn split_char_indices(&self, pat: char) -> impl Iterator<Item = (usize, &'_ str)> + '_ {
+        self.as_indexed().split_char_indices(pat)
+    }
+
+    /// Drops index data and returns the backing string pointer.
+    #[must_use]
+    pub fn into_inner(self) -> S {
+        self.buf
+    }
+
+    /// Returns a reference to the backing string pointer.
+    #[must_use]
+    pub fn as_inner(&self) -> &S {
+        &self.buf
+    }
+
+    /// Returns a reference to the backing string as a `&str`.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.buf
+    }
+}
+
+// The following lines are all trait implementations made to mirror what str does, and be compatible with str
+
+impl<S: Deref<Target = str>, O> Deref for SharedIndexedChars<S, O> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.buf
+    }
+}
+
+impl<S: Deref<Target = str>, O> AsRef<str> for SharedIndexedChars<S, O> {
+    fn as_ref(&self) -> &str {
+        self
+    }
+}
+
+impl<S: Deref<Target = str>, O> AsRef<[u8]> for SharedIndexedChars<S, O> {
+    fn as_ref(&self) -> &[u8] {
+        self.buf.as_bytes()
+    }
+}
+
+impl<S: Deref<Target = str>, O> Borrow<str> for SharedIndexedChars<S, O> {
+    fn borrow(&self) -> &str {
+        self
+    }
+}
+
+impl<S: Deref<Target = str>, O> fmt::Debug for SharedIndexedChars<S, O> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        <str as fmt::Debug>::fmt(&self.buf, f)
+    }
+}
+
+impl<S: Deref<Target = str>, O> fmt::Display for SharedIndexedChars<S, O> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        <str as fmt::Display>::fmt(&self.buf, f)
+    }
+}
+
+impl<S: Deref<Target = str>, O> Eq for SharedIndexedChars<S, O> {}
+
+impl<S: Deref<Target = str>, O> PartialEq for SharedIndexedChars<S, O> {
+    fn eq(&self, other: &Self) -> bool {
+        self.inner.same_char_count(&other.inner) && (*self.buf).eq(&*other.buf)
+    }
+}
+
+impl<S: Deref<Target = str>, O> PartialEq<str> for SharedIndexedChars<S, O> {
+    fn eq(&self, other: &str) -> bool {
+        (*self.buf).eq(other)
+    }
+}
+
+impl<S: Deref<Target = str>, O> PartialEq<SharedIndexedChars<S, O>> for str {
+    fn eq(&self, other: &SharedIndexedChars<S, O>) -> bool {
+        self.eq(&*other.buf)
+    }
+}
+
+impl<S: Deref<Target = str>, O> Ord for SharedIndexedChars<S, O> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (*self.buf).cmp(&*other.buf)
+    }
+}
+
+impl<S: Deref<Target = str>, O> PartialOrd for SharedIndexedChars<S, O> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S: Deref<Target = str>, O> PartialOrd<str> for SharedIndexedChars<S, O> {
+    fn partial_cmp(&self, other: &str) -> Option<Ordering> {
+        Some((*self.buf).cmp(other))
+    }
+}
+
+impl<S: Deref<Target = str>, O> PartialOrd<SharedIndexedChars<S, O>> for str {
+    fn partial_cmp(&self, other: &SharedIndexedChars<S, O>) -> Option<Ordering> {
+        Some(self.cmp(&*other.buf))
+    }
+}
+
+impl<S: Deref<Target = str>, O> Hash for SharedIndexedChars<S, O> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (*self.buf).hash(state);
+    }
+}
+
+#[test]
+fn clone_shares_index() {
+    use alloc::rc::Rc;
+
+    let index = SharedIndexedChars::new(Rc::<str>::from("föobär".repeat(100).as_str()));
+    let clone = index.clone();
+
+    assert!(Rc::ptr_eq(&index.buf, &clone.buf));
+    assert!(Arc::ptr_eq(&index.inner, &clone.inner));
+    assert_eq!(clone.get_char(598), Some('ä'));
+}
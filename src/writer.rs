@@ -0,0 +1,103 @@
+//! Module containing [`IndexedWriter`], for indexing a string while it is written
+
+use core::fmt;
+
+use alloc::string::String;
+
+use crate::{offset::OffsetInt, OwnedIndexedChars};
+
+/// A [`fmt::Write`] sink that indexes a string as it is written to, instead of scanning the whole string once it is done.
+///
+/// Every write extends the index in place like [`OwnedIndexedChars::push_str`],
+/// so [`finish`][IndexedWriter::finish] only has to hand over the string and its index.
+///
+/// # Examples
+/// ```rust
+/// # use char_index::IndexedWriter;
+/// use core::fmt::Write;
+///
+/// let mut writer = IndexedWriter::new();
+/// write!(writer, "föo{}", 42).unwrap();
+///
+/// let index = writer.finish();
+///
+/// assert_eq!(index, "föo42");
+/// assert_eq!(index.get_char(3), Some('4'));
+/// ```
+#[derive(Debug, Clone)]
+pub struct IndexedWriter<O = u8> {
+    /// String written so far, indexed as it grows
+    buf: OwnedIndexedChars<O>,
+}
+
+impl IndexedWriter {
+    /// Constructs a new empty [`IndexedWriter`].
+    #[must_use]
+    pub fn new() -> Self {
+        Self::new_with_width()
+    }
+}
+
+impl<O: OffsetInt> IndexedWriter<O> {
+    /// Constructs a new empty [`IndexedWriter`], storing its offsets in `O`.
+    ///
+    /// This is the same as [`new`][IndexedWriter::new] for any offset width.
+    #[must_use]
+    pub fn new_with_width() -> Self {
+        Self {
+            buf: OwnedIndexedChars::new_with_width(String::new()),
+        }
+    }
+
+    /// Returns the string written so far.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.buf
+    }
+
+    /// Consumes the writer, returning the written string and its already built index.
+    #[must_use]
+    pub fn finish(self) -> OwnedIndexedChars<O> {
+        self.buf
+    }
+}
+
+impl<O: OffsetInt> Default for IndexedWriter<O> {
+    fn default() -> Self {
+        Self::new_with_width()
+    }
+}
+
+impl<O: OffsetInt> fmt::Write for IndexedWriter<O> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.buf.push_str(s);
+        Ok(())
+    }
+
+    fn write_char(&mut self, c: char) -> fmt::Result {
+        self.buf.push(c);
+        Ok(())
+    }
+}
+
+#[test]
+fn writer() {
+    use fmt::Write;
+
+    let mut writer = IndexedWriter::<u8>::new_with_width();
+    let mut expected = String::new();
+
+    // enough non ascii chars to roll over several times
+    for i in 0..200 {
+        write!(writer, "{i}föobär💯").unwrap();
+        writer.write_char('ä').unwrap();
+        write!(expected, "{i}föobär💯ä").unwrap();
+    }
+
+    assert_eq!(writer.as_str(), expected);
+
+    let index = writer.finish();
+
+    assert!(index.validate());
+    assert_eq!(index, OwnedIndexedChars::new(expected));
+}